@@ -0,0 +1,44 @@
+use std::{env, fs};
+
+use backgammon_sim::{
+    dice::SeededDice,
+    game::{play_game, EvaluatorPlayer, Player},
+    i8_based::{Heuristic, NeuralNet, PipCount, RaceFormula, StaticEval},
+};
+
+// Plays `n_games` between players searching one ply with `a` and `b`,
+// swapping sides every game, and returns the wins of `a`.
+fn compare(a: impl StaticEval, b: impl StaticEval, n_games: u32) -> u32 {
+    let mut dice = SeededDice::new(0);
+    let mut a = EvaluatorPlayer::with_eval(1, a);
+    let mut b = EvaluatorPlayer::with_eval(1, b);
+
+    (0..n_games)
+        .filter(|i| {
+            let a_light = i % 2 == 0;
+            let players: [&mut dyn Player; 2] = if a_light {
+                [&mut a, &mut b]
+            } else {
+                [&mut b, &mut a]
+            };
+            play_game(players, &mut dice).result.winner == a_light
+        })
+        .count() as u32
+}
+
+fn main() {
+    let n_games = env::args().nth(1).map_or(100, |n| n.parse().unwrap());
+    let net: Option<NeuralNet> = env::args()
+        .nth(2)
+        .map(|path| fs::read_to_string(path).unwrap().parse().unwrap());
+
+    println!("Wins against the pip count in {n_games} games");
+    println!("race formula: {}", compare(RaceFormula, PipCount, n_games));
+    println!(
+        "heuristic:    {}",
+        compare(Heuristic::default(), PipCount, n_games)
+    );
+    if let Some(net) = net {
+        println!("neural net:   {}", compare(net, PipCount, n_games));
+    }
+}
@@ -0,0 +1,26 @@
+use std::process::ExitCode;
+
+use backgammon_sim::{differential, rng::Rng};
+
+// Usage: differential [seed] [positions]
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let seed = args.next().map_or(0, |s| s.parse().expect("Invalid seed"));
+    let positions = args
+        .next()
+        .map_or(1000, |n| n.parse().expect("Invalid position count"));
+
+    let mismatches = differential::run(&mut Rng::new(seed), positions);
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+
+    println!("{} mismatches in {positions} positions", mismatches.len());
+
+    if mismatches.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
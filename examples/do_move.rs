@@ -0,0 +1,71 @@
+use backgammon_sim::{GameState, MoveError, SPECIAL_MOVE};
+
+fn main() -> Result<(), MoveError> {
+    let state = GameState::new_with_default_setup();
+
+    let r = state.do_move(true, 100, 6);
+    println!("{r:?}");
+
+    let r = state.do_move(true, 0, 5);
+    println!("{r:?}");
+
+    let state = state.do_move(true, 0, 6)?.do_move(true, 0, 6)?;
+
+    println!("{state}");
+
+    let r = state.do_move(true, 0, 6);
+    println!("{r:?}");
+
+    let r = state.do_move(true, 18, 6);
+    println!("{r:?}");
+
+    let r = state.do_move(true, SPECIAL_MOVE, 3);
+    println!("{r:?}");
+
+    let state = state.do_move(true, 6, 2)?.do_move(false, 7, 1)?;
+    println!("{state}");
+
+    let r = state.do_move(true, SPECIAL_MOVE, 6);
+    println!("{r:?}");
+
+    let state = state.do_move(true, SPECIAL_MOVE, 4)?;
+    println!("{state}");
+
+    let state = state
+        .do_move(true, 3, 18)?
+        .do_move(true, 8, 12)?
+        .do_move(true, 11, 10)?
+        .do_move(true, 11, 10)?
+        .do_move(true, 11, 10)?
+        .do_move(true, 11, 10)?
+        .do_move(true, 11, 10)?
+        .do_move(true, 16, 3)?
+        .do_move(true, 16, 3)?
+        .do_move(true, 16, 3)?;
+    println!("{state}");
+
+    let state = state.do_move(true, 19, 5)?;
+    println!("{state}");
+
+    let r = state.do_move(true, 19, 6);
+    println!("{r:?}");
+
+    let state = state
+        .do_move(false, 23, 20)?
+        .do_move(false, 23, 20)?
+        .do_move(false, 12, 12)?
+        .do_move(false, 12, 12)?
+        .do_move(false, 12, 12)?
+        .do_move(false, 12, 12)?
+        .do_move(false, 12, 12)?
+        .do_move(false, 7, 3)?
+        .do_move(false, 7, 3)?
+        .do_move(false, 6, 3)?
+        .do_move(false, 4, 5)?;
+    println!("{state}");
+
+    let r = state.do_move(false, 4, 6);
+    println!("{r:?}");
+
+    Ok(())
+}
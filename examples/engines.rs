@@ -0,0 +1,36 @@
+use backgammon_sim::{enum_based, i8_based, Backgammon};
+
+fn count_plays<B: Backgammon>(turn: bool) -> [[usize; 6]; 6] {
+    let state = B::default_setup();
+
+    let mut counts = [[0; 6]; 6];
+
+    for d1 in 1..=6 {
+        for d2 in d1..=6 {
+            counts[d1 as usize - 1][d2 as usize - 1] =
+                state.legal_plays(turn, [d1, d2]).len();
+        }
+    }
+
+    counts
+}
+
+fn main() {
+    for turn in [true, false] {
+        let i8_counts = count_plays::<i8_based::GameState>(turn);
+        let enum_counts = count_plays::<enum_based::GameState>(turn);
+
+        println!("turn: {turn}");
+        for d1 in 0..6 {
+            for d2 in d1..6 {
+                println!(
+                    "{}{}: i8_based {:3}, enum_based {:3}",
+                    d1 + 1,
+                    d2 + 1,
+                    i8_counts[d1][d2],
+                    enum_counts[d1][d2]
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use std::time::Instant;
+
+use backgammon_sim::{Evaluator, GameState};
+
+fn main() {
+    let state = GameState::new_with_default_setup();
+
+    let mut ev = Evaluator::new();
+
+    let t = Instant::now();
+    let eval = ev.get_brute_force_eval(state, true, 3);
+    let t = t.elapsed();
+
+    println!("{eval}");
+    println!("took {t:?}");
+}
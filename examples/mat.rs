@@ -0,0 +1,24 @@
+use std::env;
+
+use backgammon_sim::{
+    dice::SeededDice,
+    game::{mat::MatchFile, play_game, PipCountPlayer, RandomPlayer},
+    rng::Rng,
+};
+
+fn main() {
+    let n_games: usize = env::args().nth(1).map_or(3, |n| n.parse().unwrap());
+
+    let mut dice = SeededDice::new(0);
+    let mut light = PipCountPlayer;
+    let mut dark = RandomPlayer::new(Rng::new(1));
+
+    let records: Vec<_> = (0..n_games)
+        .map(|_| play_game([&mut light, &mut dark], &mut dice))
+        .collect();
+
+    print!(
+        "{}",
+        MatchFile::from_records(0, ["Pip count", "Random"], &records)
+    );
+}
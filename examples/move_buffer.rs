@@ -0,0 +1,19 @@
+use backgammon_sim::{GameState, MoveBuffer};
+
+fn main() {
+    let state = GameState::new_with_default_setup();
+
+    println!("{state}");
+
+    let mut moves = MoveBuffer::new();
+
+    moves.generate(true, state, [1, 1]);
+
+    println!("{moves:?}");
+
+    moves.generate(true, state, [5, 5]);
+
+    for new_state in moves.state_iterator() {
+        println!("{new_state}");
+    }
+}
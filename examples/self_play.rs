@@ -0,0 +1,36 @@
+use std::{env, time::Instant};
+
+use backgammon_sim::{
+    dice::SeededDice,
+    game::{play_game, PipCountPlayer, RandomPlayer},
+    rng::Rng,
+};
+
+fn main() {
+    let n_games: u32 = env::args().nth(1).map_or(1000, |n| n.parse().unwrap());
+
+    let mut dice = SeededDice::new(0);
+    let mut light = PipCountPlayer;
+    let mut dark = RandomPlayer::new(Rng::new(1));
+
+    let mut points = [0; 2];
+    let mut wins = [0; 2];
+    let mut n_turns = 0;
+
+    let t = Instant::now();
+    for _ in 0..n_games {
+        let record = play_game([&mut light, &mut dark], &mut dice);
+
+        let winner = (!record.result.winner) as usize;
+        wins[winner] += 1;
+        points[winner] += record.result.points();
+        n_turns += record.turns.len();
+    }
+    let t = t.elapsed();
+
+    println!("Pip count (light) vs random (dark), {n_games} games");
+    println!("wins:   {wins:?}");
+    println!("points: {points:?}");
+    println!("average turns: {}", n_turns as f64 / n_games as f64);
+    println!("took {t:?}");
+}
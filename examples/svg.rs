@@ -0,0 +1,12 @@
+use std::env;
+
+use backgammon_sim::{match_state::MatchState, render::SvgRenderer};
+
+fn main() {
+    let id = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "4HPwATDgc/ABMA:cAkAAAAAAAAA".to_owned());
+    let state: MatchState = id.parse().unwrap_or_else(|e| panic!("{e}"));
+
+    print!("{}", SvgRenderer::for_match(&state).render(&state.position));
+}
@@ -0,0 +1,48 @@
+use std::{env, time::Instant};
+
+use backgammon_sim::{
+    i8_based::NeuralNet, rng::Rng, Backgammon, Evaluator, GameState, MoveBuffer,
+};
+
+// Step size of each update.
+const RATE: f64 = 0.1;
+
+fn main() {
+    let arg =
+        |i, default| env::args().nth(i).map_or(default, |n| n.parse().unwrap());
+    let n_games = arg(1, 1000);
+    let hidden = arg(2, 40);
+
+    let mut rng = Rng::new(0);
+    let mut net = NeuralNet::new(hidden, &mut rng);
+    let mut moves = MoveBuffer::new();
+
+    let t = Instant::now();
+    for game in 1..=n_games {
+        let mut state = GameState::new_with_default_setup();
+        let mut turn = rng.below(2) == 0;
+
+        // Each position learns the evaluation of the best play from it, which
+        // is exact once the game is over.
+        loop {
+            let dice = rng.roll_dice();
+            let (_, new_state, eval) = Evaluator::with_eval(&net)
+                .get_best_move(state, turn, dice, 1, &mut moves)
+                .unwrap();
+
+            net.train(&state, turn, &eval, RATE);
+
+            if new_state.result().is_some() {
+                break;
+            }
+            state = new_state;
+            turn = !turn;
+        }
+
+        if game % 100 == 0 {
+            eprintln!("{game} games, {:?}", t.elapsed());
+        }
+    }
+
+    print!("{net}");
+}
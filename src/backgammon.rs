@@ -0,0 +1,166 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+// Shared interface for the two board representations.
+//
+// Players are a `bool` following the i8_based convention:
+// Light: (true, moves from point 0 towards 23, home on 18..24)
+// Dark:  (false, moves from point 23 towards 0, home on 0..6)
+// Per-player arrays (`bar`, `borne_off`, `pip_counts`) are indexed
+// [light, dark].
+pub trait Backgammon: Clone + Eq + Hash + Debug + Display {
+    /// A single checker movement in the form the engine applies it.
+    type Move: Copy + Debug + Eq;
+
+    fn empty() -> Self;
+
+    fn default_setup() -> Self;
+
+    /// Builds a position from signed checker counts per point (see
+    /// [`Backgammon::checkers`]) and the per-player bar and borne off counts.
+    /// No validation is done.
+    fn from_parts(checkers: [i8; 24], bar: [u8; 2], borne_off: [u8; 2])
+        -> Self;
+
+    /// Checkers on `point`, positive for Light and negative for Dark.
+    fn checkers(&self, point: usize) -> i8;
+
+    fn bar(&self) -> [u8; 2];
+
+    fn borne_off(&self) -> [u8; 2];
+
+    fn pip_counts(&self) -> [u32; 2];
+
+    /// All moves `turn` can make with a single die.
+    fn legal_moves(&self, turn: bool, die: u8) -> Vec<Self::Move>;
+
+    /// Applies `m` for `turn`, returning `None` if it is not a legal single
+    /// die move in this position.
+    fn apply_move(&self, turn: bool, m: Self::Move) -> Option<Self>;
+
+    /// Every distinct position `turn` can reach by playing `dice`. If no
+    /// checker can move, the only entry is the current position.
+    fn legal_plays(&self, turn: bool, dice: [u8; 2]) -> Vec<Self>;
+
+    fn winner(&self) -> Option<bool> {
+        match self.borne_off() {
+            [15, _] => Some(true),
+            [_, 15] => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Checks that each player has exactly 15 checkers between the board, the
+    /// bar and those borne off.
+    fn validate(&self) -> Result<(), PositionError> {
+        for player in [true, false] {
+            // Light is index 0.
+            let i = (!player) as usize;
+            let on_board: u32 = (0..24)
+                .map(|point| self.checkers(point))
+                .filter(|&n| if player { n > 0 } else { n < 0 })
+                .map(|n| n.unsigned_abs() as u32)
+                .sum();
+            let count =
+                on_board + self.bar()[i] as u32 + self.borne_off()[i] as u32;
+
+            if count != 15 {
+                return Err(PositionError::CheckerCount { player, count });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_game_over(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    /// How the game ended, or `None` if it is still going.
+    fn result(&self) -> Option<GameResult> {
+        let winner = self.winner()?;
+        // Light is index 0, so the loser is at the winner's index as a usize.
+        let loser = winner as usize;
+
+        let kind = if self.borne_off()[loser] > 0 {
+            WinKind::Single
+        } else {
+            let mut winner_home = if winner { 18..24 } else { 0..6 };
+            let in_winner_home = winner_home.any(|i| {
+                let n = self.checkers(i);
+                if winner {
+                    n < 0
+                } else {
+                    n > 0
+                }
+            });
+
+            if self.bar()[loser] > 0 || in_winner_home {
+                WinKind::Backgammon
+            } else {
+                WinKind::Gammon
+            }
+        };
+
+        Some(GameResult { winner, kind })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum WinKind {
+    Single,
+    /// The loser has not borne off any checkers.
+    Gammon,
+    /// A gammon where the loser still has checkers on the bar or in the
+    /// winner's home board.
+    Backgammon,
+}
+
+impl WinKind {
+    pub fn points(self) -> u32 {
+        match self {
+            WinKind::Single => 1,
+            WinKind::Gammon => 2,
+            WinKind::Backgammon => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameResult {
+    pub winner: bool,
+    pub kind: WinKind,
+}
+
+impl GameResult {
+    /// Points won, before the doubling cube is applied.
+    pub fn points(&self) -> u32 {
+        self.kind.points()
+    }
+}
+
+/// Why a position was rejected when converting between engines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PositionError {
+    /// `player` does not have exactly 15 checkers.
+    CheckerCount { player: bool, count: u32 },
+}
+
+impl Display for PositionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::CheckerCount { player, count } => {
+                let player = if player { "Light" } else { "Dark" };
+                write!(f, "{player} has {count} checkers instead of 15")
+            }
+        }
+    }
+}
+
+impl Error for PositionError {}
@@ -0,0 +1,39 @@
+//! The standard base64 alphabet, as used by GNU Backgammon's IDs. Padding is
+//! never written or expected.
+
+const ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity((bytes.len() * 4).div_ceil(3));
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+
+        for i in 0..=chunk.len() {
+            text.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+
+    text
+}
+
+/// Decodes `text` into the start of `bytes`, which must have room for all of
+/// its bits. Returns the first character outside the alphabet as the error.
+pub(crate) fn decode(text: &str, bytes: &mut [u8]) -> Result<(), char> {
+    for (i, c) in text.chars().enumerate() {
+        let value = ALPHABET.iter().position(|&b| b as char == c).ok_or(c)?;
+
+        for j in 0..6 {
+            if value & (32 >> j) != 0 {
+                let bit = 6 * i + j;
+                bytes[bit / 8] |= 0x80 >> (bit % 8);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+//! The doubling cube and cube decisions.
+//!
+//! Cubeful equities come from cubeless win chances with Janowski's model: a
+//! mix of the equity with a dead cube, which is never turned again, and with
+//! a fully live cube, which is turned at exactly the right moment.
+
+use std::fmt::Display;
+
+use crate::probabilities::Probabilities;
+
+/// How much of the live cube's value is realised. A common figure for
+/// positions with contact.
+pub const CUBE_EFFICIENCY: f64 = 0.68;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cube {
+    pub value: u32,
+    /// `None` while the cube is centred.
+    pub owner: Option<bool>,
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cube {
+    /// The highest value the cube can reach, as in GNU Backgammon.
+    pub const MAX_VALUE: u32 = 1 << 15;
+
+    /// A centred cube on 1.
+    pub fn new() -> Self {
+        Self {
+            value: 1,
+            owner: None,
+        }
+    }
+
+    pub fn is_centred(&self) -> bool {
+        self.owner.is_none()
+    }
+
+    pub fn may_double(&self, player: bool) -> bool {
+        self.value < Self::MAX_VALUE
+            && self.owner.is_none_or(|owner| owner == player)
+    }
+
+    /// Whether a double of this cube could be beavered without passing
+    /// [`MAX_VALUE`](Self::MAX_VALUE).
+    pub fn may_beaver(&self) -> bool {
+        self.value * 4 <= Self::MAX_VALUE
+    }
+
+    /// The cube after `player` takes a double.
+    pub fn taken(self, player: bool) -> Self {
+        Self {
+            value: self.value * 2,
+            owner: Some(player),
+        }
+    }
+
+    /// The cube after `player` beavers a double: they take it and redouble
+    /// at once, keeping it.
+    pub fn beavered(self, player: bool) -> Self {
+        Self {
+            value: self.value * 4,
+            owner: Some(player),
+        }
+    }
+}
+
+/// How a player answers a double.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CubeResponse {
+    Take,
+    Drop,
+    /// Take and redouble, keeping the cube. Only allowed in money play.
+    Beaver,
+}
+
+/// Cubeless chances of the player on roll, with the average number of points
+/// a win and a loss are worth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chances {
+    pub win: f64,
+    /// At least 1.
+    pub win_value: f64,
+    /// At least 1.
+    pub loss_value: f64,
+}
+
+impl Chances {
+    /// Chances with only single games.
+    pub fn single(win: f64) -> Self {
+        Self {
+            win,
+            win_value: 1.0,
+            loss_value: 1.0,
+        }
+    }
+
+    /// Equity per point of cube when the cube is never used again.
+    pub fn cubeless_equity(&self) -> f64 {
+        self.win * (self.win_value + self.loss_value) - self.loss_value
+    }
+
+    /// The lowest chance of winning at which the opponent of a live cube
+    /// should still take.
+    pub fn take_point(&self) -> f64 {
+        (self.loss_value - 0.5) / (self.win_value + self.loss_value + 0.5)
+    }
+
+    /// The chance of winning at which a live cube is cashed.
+    pub fn cash_point(&self) -> f64 {
+        (self.loss_value + 1.0) / (self.win_value + self.loss_value + 0.5)
+    }
+
+    /// Equity per point of cube for the player on roll, with the cube in the
+    /// hands of `owner`: the player on roll (`Some(true)`), their opponent
+    /// (`Some(false)`) or neither.
+    pub fn cubeful_equity(&self, owner: Option<bool>, efficiency: f64) -> f64 {
+        let [p, w, l] = [self.win, self.win_value, self.loss_value];
+        let [take, cash] = [self.take_point(), self.cash_point()];
+
+        // Live cube equity is linear between the points where the cube is
+        // turned and dropped. Beyond them the game is played on for the
+        // gammons, up to the cubeless result of a sure win or loss.
+        let played_on_loss = -l + (l - 1.0) * p / take;
+        let played_on_win = 1.0 + (w - 1.0) * (p - cash) / (1.0 - cash);
+
+        let live = match owner {
+            None | Some(false) if p < take => played_on_loss,
+            None | Some(true) if p > cash => played_on_win,
+            None => -1.0 + 2.0 * (p - take) / (cash - take),
+            Some(true) => -l + p * (1.0 + l) / cash,
+            Some(false) => -1.0 + (p - take) * (w + 1.0) / (1.0 - take),
+        };
+
+        efficiency * live + (1.0 - efficiency) * self.cubeless_equity()
+    }
+}
+
+impl From<&Probabilities> for Chances {
+    fn from(probs: &Probabilities) -> Self {
+        // The average value of a win or loss, or 1 if it never happens.
+        let value = |p: f64, gammon: f64, backgammon: f64| {
+            if p > 0.0 {
+                1.0 + (gammon + backgammon) / p
+            } else {
+                1.0
+            }
+        };
+
+        Self {
+            win: probs.win,
+            win_value: value(probs.win, probs.win_gammon, probs.win_backgammon),
+            loss_value: value(
+                probs.lose(),
+                probs.lose_gammon,
+                probs.lose_backgammon,
+            ),
+        }
+    }
+}
+
+/// Cubeful equities of the cube actions open to the player on roll, per point
+/// of the cube before doubling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubefulEquity {
+    pub no_double: f64,
+    pub double_take: f64,
+    pub double_pass: f64,
+}
+
+impl CubefulEquity {
+    /// The equities for `chances` with the player on roll able to double
+    /// a cube held by `owner`, as in [`Chances::cubeful_equity`].
+    pub fn new(chances: &Chances, owner: Option<bool>) -> Self {
+        Self {
+            no_double: chances.cubeful_equity(owner, CUBE_EFFICIENCY),
+            double_take: 2.0
+                * chances.cubeful_equity(Some(false), CUBE_EFFICIENCY),
+            double_pass: 1.0,
+        }
+    }
+
+    /// The opponent's best answer to a double.
+    pub fn response(&self, beavers: bool) -> CubeResponse {
+        if beavers && self.double_take < 0.0 {
+            CubeResponse::Beaver
+        } else if self.double_take <= self.double_pass {
+            CubeResponse::Take
+        } else {
+            CubeResponse::Drop
+        }
+    }
+
+    /// The equity of doubling, given the opponent answers it correctly.
+    /// Beavers are not counted.
+    pub fn doubled(&self) -> f64 {
+        self.double_take.min(self.double_pass)
+    }
+
+    pub fn should_double(&self) -> bool {
+        self.doubled() > self.no_double
+    }
+
+    /// The equity with correct cube play by both sides.
+    pub fn equity(&self) -> f64 {
+        self.no_double.max(self.doubled())
+    }
+}
+
+impl Display for CubefulEquity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "No double:   {:+.3}", self.no_double)?;
+        writeln!(f, "Double/take: {:+.3}", self.double_take)?;
+        write!(f, "Double/pass: {:+.3}", self.double_pass)
+    }
+}
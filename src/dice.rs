@@ -0,0 +1,208 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::rng::Rng;
+
+/// Where the dice of a game come from.
+pub trait DiceSource {
+    fn roll(&mut self) -> [u8; 2];
+}
+
+/// Pseudo random dice. Two sources created with the same seed roll the same
+/// sequence on every platform.
+#[derive(Debug, Clone)]
+pub struct SeededDice {
+    seed: u64,
+    rng: Rng,
+}
+
+impl SeededDice {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Seeded from the system clock. The seed can be read back with
+    /// [`SeededDice::seed`] to replay the rolls later.
+    pub fn from_time() -> Self {
+        Self::new(Rng::from_time().next_u64())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl DiceSource for SeededDice {
+    fn roll(&mut self) -> [u8; 2] {
+        self.rng.roll_dice()
+    }
+}
+
+/// Replays a fixed sequence of rolls.
+///
+/// Parses from text with one roll per line, written like `31`, `3 1` or
+/// `3-1`. Empty lines and anything after a `#` are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptedDice {
+    rolls: Vec<[u8; 2]>,
+    next: usize,
+}
+
+impl ScriptedDice {
+    /// # Panics
+    ///
+    /// If any die is outside 1..=6.
+    pub fn new(rolls: impl IntoIterator<Item = [u8; 2]>) -> Self {
+        let rolls: Vec<_> = rolls.into_iter().collect();
+        assert!(
+            rolls.iter().flatten().all(|d| (1..=6).contains(d)),
+            "Dice must be between 1 and 6"
+        );
+
+        Self { rolls, next: 0 }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.rolls.len() - self.next
+    }
+}
+
+impl DiceSource for ScriptedDice {
+    /// # Panics
+    ///
+    /// When all rolls have been used.
+    fn roll(&mut self) -> [u8; 2] {
+        let roll = *self
+            .rolls
+            .get(self.next)
+            .expect("Scripted dice ran out of rolls");
+        self.next += 1;
+        roll
+    }
+}
+
+impl FromStr for ScriptedDice {
+    type Err = ParseRollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rolls = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            if let Some(roll) = parse_roll_line(line, i + 1)? {
+                rolls.push(roll);
+            }
+        }
+
+        Ok(Self::new(rolls))
+    }
+}
+
+/// Reads rolls lazily from a file in the [`ScriptedDice`] format.
+#[derive(Debug)]
+pub struct FileDice<R = BufReader<File>> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl FileDice {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> FileDice<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+
+    /// The next roll, or `None` at the end of the file.
+    pub fn try_roll(&mut self) -> io::Result<Option<[u8; 2]>> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+
+            let roll = parse_roll_line(&self.line, self.line_number)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            if roll.is_some() {
+                return Ok(roll);
+            }
+        }
+    }
+}
+
+impl<R: BufRead> DiceSource for FileDice<R> {
+    /// # Panics
+    ///
+    /// On read or parse errors and at the end of the file.
+    fn roll(&mut self) -> [u8; 2] {
+        match self.try_roll() {
+            Ok(Some(roll)) => roll,
+            Ok(None) => panic!("Dice file ran out of rolls"),
+            Err(e) => panic!("Failed to read dice file: {e}"),
+        }
+    }
+}
+
+/// A line of a dice script that is not a valid roll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRollError {
+    pub line_number: usize,
+    pub line: String,
+}
+
+impl Display for ParseRollError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid roll on line {}: {:?}",
+            self.line_number, self.line
+        )
+    }
+}
+
+impl Error for ParseRollError {}
+
+fn parse_roll_line(
+    line: &str,
+    line_number: usize,
+) -> Result<Option<[u8; 2]>, ParseRollError> {
+    let text = line.split('#').next().unwrap_or_default().trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    let err = || ParseRollError {
+        line_number,
+        line: line.trim_end().to_owned(),
+    };
+
+    let mut dice = text.chars().filter(|&c| !matches!(c, ' ' | '-' | ',')).map(
+        |c| match c {
+            '1'..='6' => Ok(c as u8 - b'0'),
+            _ => Err(err()),
+        },
+    );
+
+    match (dice.next(), dice.next(), dice.next()) {
+        (Some(d1), Some(d2), None) => Ok(Some([d1?, d2?])),
+        _ => Err(err()),
+    }
+}
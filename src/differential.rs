@@ -0,0 +1,266 @@
+//! Differential testing of [`enum_based`] against [`i8_based`].
+//!
+//! Both engines are given the same position and dice, and the sets of
+//! positions they can reach are compared. A position reached by two plays
+//! should only be listed once, so repeats count as a disagreement too. Any
+//! disagreement is shrunk to a
+//! small position that still shows it before being reported.
+
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{backgammon::Backgammon, enum_based, i8_based, rng::Rng};
+
+/// A position and roll where the two engines reach different positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub state: i8_based::GameState,
+    pub turn: bool,
+    pub dice: [u8; 2],
+    /// Reachable according to `i8_based` only.
+    pub only_i8: Vec<i8_based::GameState>,
+    /// Reachable according to `enum_based` only.
+    pub only_enum: Vec<i8_based::GameState>,
+    /// Listed more than once by `i8_based`.
+    pub repeated_i8: Vec<i8_based::GameState>,
+    /// Listed more than once by `enum_based`.
+    pub repeated_enum: Vec<i8_based::GameState>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let player = if self.turn { "Light" } else { "Dark" };
+        let [d1, d2] = self.dice;
+
+        writeln!(f, "{player} to play {d1}-{d2} from")?;
+        writeln!(f, "{}", self.state)?;
+
+        for (engine, states) in
+            [("i8_based", &self.only_i8), ("enum_based", &self.only_enum)]
+        {
+            writeln!(f, "Only reachable with {engine}: {}", states.len())?;
+            for state in states {
+                writeln!(f, "{state}")?;
+            }
+        }
+
+        for (engine, states) in [
+            ("i8_based", &self.repeated_i8),
+            ("enum_based", &self.repeated_enum),
+        ] {
+            writeln!(f, "Repeated by {engine}: {}", states.len())?;
+            for state in states {
+                writeln!(f, "{state}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares the positions both engines reach from `state` with `dice`.
+pub fn compare(
+    state: &i8_based::GameState,
+    turn: bool,
+    dice: [u8; 2],
+) -> Option<Mismatch> {
+    let mut moves = i8_based::MoveBuffer::new();
+    moves.generate(turn, *state, dice);
+    let i8_states: Vec<_> = moves.state_iterator().collect();
+
+    let enum_state: enum_based::GameState = convert(state);
+    let mut plays = Vec::new();
+    // `enum_based` uses `false` for Light.
+    enum_state.get_possible_moves_double(
+        !turn,
+        dice,
+        &mut plays,
+        &mut Vec::new(),
+        &mut Vec::new(),
+    );
+
+    let mut enum_states: Vec<_> = plays
+        .iter()
+        .map(|play| {
+            let mut new_state = enum_state.clone();
+            for &m in play {
+                new_state.do_move(m);
+            }
+            convert(&new_state)
+        })
+        .collect();
+    if enum_states.is_empty() {
+        enum_states.push(*state);
+    }
+
+    let (i8_set, repeated_i8) = dedup(&i8_states);
+    let (enum_set, repeated_enum) = dedup(&enum_states);
+
+    if i8_set == enum_set && repeated_i8.is_empty() && repeated_enum.is_empty()
+    {
+        return None;
+    }
+
+    let difference = |a: &HashSet<_>, b| {
+        let mut states: Vec<_> = a.difference(b).copied().collect();
+        sort(&mut states);
+        states
+    };
+
+    Some(Mismatch {
+        state: *state,
+        turn,
+        dice,
+        only_i8: difference(&i8_set, &enum_set),
+        only_enum: difference(&enum_set, &i8_set),
+        repeated_i8,
+        repeated_enum,
+    })
+}
+
+// The distinct positions in `states`, and those listed more than once.
+fn dedup(
+    states: &[i8_based::GameState],
+) -> (HashSet<i8_based::GameState>, Vec<i8_based::GameState>) {
+    let mut set = HashSet::new();
+    let mut repeated = Vec::new();
+
+    for &state in states {
+        if !set.insert(state) && !repeated.contains(&state) {
+            repeated.push(state);
+        }
+    }
+
+    sort(&mut repeated);
+    (set, repeated)
+}
+
+fn sort(states: &mut [i8_based::GameState]) {
+    states.sort_by_key(|s| s.get_tot_dist());
+}
+
+/// Shrinks `mismatch` by bearing off checkers one at a time, for as long as
+/// the engines still disagree with the same dice.
+pub fn minimise(mut mismatch: Mismatch) -> Mismatch {
+    'shrink: loop {
+        for smaller in simplifications(mismatch.state) {
+            if let Some(m) = compare(&smaller, mismatch.turn, mismatch.dice) {
+                mismatch = m;
+                continue 'shrink;
+            }
+        }
+
+        return mismatch;
+    }
+}
+
+/// Checks every roll for both players in `positions` random positions,
+/// returning the minimised mismatches found.
+pub fn run(rng: &mut Rng, positions: usize) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+
+    for _ in 0..positions {
+        let state = random_position(rng);
+
+        for turn in [true, false] {
+            for d1 in 1..=6 {
+                for d2 in d1..=6 {
+                    if let Some(m) = compare(&state, turn, [d1, d2]) {
+                        mismatches.push(minimise(m));
+                    }
+                }
+            }
+        }
+    }
+
+    mismatches
+}
+
+/// A position with each player's 15 checkers spread over the board, the bar
+/// and borne off. Checkers are kept close to home often enough to exercise
+/// bearing off.
+pub fn random_position(rng: &mut Rng) -> i8_based::GameState {
+    let mut checkers = [0i8; 24];
+    let mut bar = [0; 2];
+    let mut borne_off = [0; 2];
+
+    for player in 0..2 {
+        // Light moves towards 23 and Dark towards 0.
+        let home_only = rng.below(3) == 0;
+        let sign = if player == 0 { 1 } else { -1 };
+
+        for _ in 0..15 {
+            match rng.below(20) {
+                0 if !home_only => bar[player] += 1,
+                1 | 2 => borne_off[player] += 1,
+                _ => {
+                    let open = |range: std::ops::Range<usize>| {
+                        range
+                            .map(|distance| {
+                                if player == 0 {
+                                    23 - distance
+                                } else {
+                                    distance
+                                }
+                            })
+                            .filter(|&i| checkers[i] * sign >= 0)
+                            .collect::<Vec<_>>()
+                    };
+
+                    // The opponent can block all of the home board, but not
+                    // every point.
+                    let mut points = open(if home_only { 0..6 } else { 0..24 });
+                    if points.is_empty() {
+                        points = open(0..24);
+                    }
+
+                    checkers[points[rng.below(points.len())]] += sign;
+                }
+            }
+        }
+    }
+
+    convert_parts(checkers, bar, borne_off)
+}
+
+// Each position with one checker fewer on the bar or on a point.
+fn simplifications(
+    state: i8_based::GameState,
+) -> impl Iterator<Item = i8_based::GameState> {
+    let checkers: [i8; 24] = std::array::from_fn(|i| state.checkers(i));
+    let bar = state.bar();
+    let borne_off = state.borne_off();
+
+    let from_bar = (0..2).filter(move |&p| bar[p] > 0).map(move |p| {
+        let (mut bar, mut borne_off) = (bar, borne_off);
+        bar[p] -= 1;
+        borne_off[p] += 1;
+        convert_parts(checkers, bar, borne_off)
+    });
+
+    let from_points =
+        (0..24).filter(move |&i| checkers[i] != 0).map(move |i| {
+            let (mut checkers, mut borne_off) = (checkers, borne_off);
+            // Light is index 0.
+            borne_off[(checkers[i] < 0) as usize] += 1;
+            checkers[i] -= checkers[i].signum();
+            convert_parts(checkers, bar, borne_off)
+        });
+
+    from_bar.chain(from_points)
+}
+
+fn convert<A: Backgammon, B: Backgammon>(state: &A) -> B {
+    B::from_parts(
+        std::array::from_fn(|i| state.checkers(i)),
+        state.bar(),
+        state.borne_off(),
+    )
+}
+
+fn convert_parts(
+    checkers: [i8; 24],
+    bar: [u8; 2],
+    borne_off: [u8; 2],
+) -> i8_based::GameState {
+    Backgammon::from_parts(checkers, bar, borne_off)
+}
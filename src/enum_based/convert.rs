@@ -0,0 +1,68 @@
+//! Conversions to and from the `i8_based` representation.
+//!
+//! Both store points by the same index with Light counted positive, and
+//! neither can hold checkers of both players on one point, so a position
+//! only has to be checked for the right number of checkers.
+
+use crate::{
+    backgammon::{Backgammon, PositionError},
+    i8_based,
+};
+
+use super::{GameState, Tile};
+
+impl From<Tile> for i8 {
+    fn from(tile: Tile) -> i8 {
+        match tile {
+            Tile::Empty => 0,
+            Tile::Light(n) => n as i8,
+            Tile::Dark(n) => -(n as i8),
+        }
+    }
+}
+
+impl From<i8> for Tile {
+    fn from(n: i8) -> Tile {
+        match n {
+            0 => Tile::Empty,
+            1..=127 => Tile::Light(n as u8),
+            _ => Tile::Dark(n.unsigned_abs()),
+        }
+    }
+}
+
+impl TryFrom<&GameState> for i8_based::GameState {
+    type Error = PositionError;
+
+    fn try_from(state: &GameState) -> Result<Self, PositionError> {
+        state.validate()?;
+
+        Ok(Backgammon::from_parts(
+            state.tiles.map(i8::from),
+            state.captured,
+            state.finished,
+        ))
+    }
+}
+
+impl TryFrom<GameState> for i8_based::GameState {
+    type Error = PositionError;
+
+    fn try_from(state: GameState) -> Result<Self, PositionError> {
+        Self::try_from(&state)
+    }
+}
+
+impl TryFrom<i8_based::GameState> for GameState {
+    type Error = PositionError;
+
+    fn try_from(state: i8_based::GameState) -> Result<Self, PositionError> {
+        state.validate()?;
+
+        Ok(Self {
+            tiles: std::array::from_fn(|i| Tile::from(state.checkers(i))),
+            captured: state.bar(),
+            finished: state.borne_off(),
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use crate::backgammon::Backgammon;
+
+use super::{GameState, Tile};
+
+// The enum based engine uses `false` for Light, so every `turn` coming through
+// the trait is flipped before it reaches the inherent methods.
+impl Backgammon for GameState {
+    // [from, to], with SPECIAL_MOVE standing in for the bar as `from` and for
+    // bearing off as `to`.
+    type Move = [u8; 2];
+
+    fn empty() -> Self {
+        Self::new()
+    }
+
+    fn default_setup() -> Self {
+        Self::new_with_default_setup()
+    }
+
+    fn from_parts(
+        checkers: [i8; 24],
+        bar: [u8; 2],
+        borne_off: [u8; 2],
+    ) -> Self {
+        Self {
+            tiles: checkers.map(Tile::from),
+            captured: bar,
+            finished: borne_off,
+        }
+    }
+
+    fn checkers(&self, point: usize) -> i8 {
+        self.tiles[point].into()
+    }
+
+    fn bar(&self) -> [u8; 2] {
+        self.captured
+    }
+
+    fn borne_off(&self) -> [u8; 2] {
+        self.finished
+    }
+
+    fn pip_counts(&self) -> [u32; 2] {
+        self.get_tot_dist()
+    }
+
+    fn legal_moves(&self, turn: bool, die: u8) -> Vec<[u8; 2]> {
+        let mut moves = Vec::new();
+        self.get_possible_moves(!turn, die, &mut moves);
+        moves
+    }
+
+    fn apply_move(&self, turn: bool, m: [u8; 2]) -> Option<Self> {
+        let mut moves = Vec::new();
+
+        (1..=6)
+            .any(|die| {
+                self.get_possible_moves(!turn, die, &mut moves);
+                moves.contains(&m)
+            })
+            .then(|| {
+                let mut state = self.clone();
+                state.do_move(m);
+                state
+            })
+    }
+
+    fn legal_plays(&self, turn: bool, dice: [u8; 2]) -> Vec<Self> {
+        let mut moves = Vec::new();
+        self.get_possible_moves_double(
+            !turn,
+            dice,
+            &mut moves,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        );
+
+        if moves.is_empty() {
+            return vec![self.clone()];
+        }
+
+        moves
+            .iter()
+            .map(|ms| {
+                let mut state = self.clone();
+                for &m in ms {
+                    state.do_move(m);
+                }
+                state
+            })
+            .collect()
+    }
+}
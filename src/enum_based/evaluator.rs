@@ -2,8 +2,12 @@ use std::collections::HashMap;
 
 use arrayvec::ArrayVec;
 
+use crate::backgammon::Backgammon;
+
 use super::GameState;
 
+const WIN_EVAL: f64 = 1000.0;
+
 impl GameState {
     fn get_net_dist(&self) -> i32 {
         let [light_dist, dark_dist] = self.get_tot_dist();
@@ -11,8 +15,22 @@ impl GameState {
         (light_dist as i32) - (dark_dist as i32)
     }
 
+    // Light wins are negative, matching the sign of `get_net_dist`.
+    fn get_terminal_eval(&self) -> Option<f64> {
+        self.result().map(|result| {
+            let eval = WIN_EVAL * result.points() as f64;
+            if result.winner {
+                -eval
+            } else {
+                eval
+            }
+        })
+    }
+
     pub fn get_brute_force_eval(&self, turn: bool, depth: u32) -> f64 {
-        if depth == 0 {
+        if let Some(eval) = self.get_terminal_eval() {
+            eval
+        } else if depth == 0 {
             self.get_net_dist() as f64
         } else {
             let mut eval = 0.0;
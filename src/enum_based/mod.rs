@@ -1,5 +1,7 @@
-mod game_display;
+mod convert;
+mod engine;
 mod evaluator;
+mod game_display;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Tile {
@@ -8,7 +10,7 @@ pub enum Tile {
     Dark(u8),
 }
 
-use std::{collections::HashMap, mem::transmute};
+use std::collections::HashMap;
 
 use arrayvec::ArrayVec;
 use Tile::*;
@@ -30,10 +32,6 @@ impl Default for GameState {
     }
 }
 
-fn count_trues<const N: usize>(bits: &[bool; N]) -> usize {
-    bits.iter().filter(|&&x| x).count()
-}
-
 impl GameState {
     pub fn new() -> Self {
         Self {
@@ -63,7 +61,7 @@ impl GameState {
 
         for t in &self.tiles[..6] {
             if let Light(_) = t {
-                home[1] = false;
+                home[0] = false;
             }
         }
 
@@ -77,7 +75,7 @@ impl GameState {
 
         for t in &self.tiles[18..] {
             if let Dark(_) = t {
-                home[0] = false;
+                home[1] = false;
             }
         }
 
@@ -98,85 +96,86 @@ impl GameState {
         ans
     }
 
-    // Returns true if moves are wasteful
     pub fn get_possible_moves(
         &self,
         turn: bool, // false => Light, true => Dark
         die: u8,
         moves: &mut Vec<[u8; 2]>,
-    ) -> bool {
-        let mut wasteful = false;
-
+    ) {
         moves.clear();
 
         let turn_ind = if turn { 1 } else { 0 };
 
         let move_dir = if turn { -1 } else { 1 };
 
+        let can_land = |t: Option<&Tile>| {
+            matches!(
+                (t, turn),
+                (Some(Empty), _)
+                    | (Some(Light(_)), false)
+                    | (Some(Dark(1)), false)
+                    | (Some(Dark(_)), true)
+                    | (Some(Light(1)), true)
+            )
+        };
+
         if self.captured[turn_ind] == 0 {
             for i in 0..24 {
                 if let (Light(_), false) | (Dark(_), true) =
                     (self.tiles[i], turn)
                 {
-                    if let (Some(Empty), _)
-                    | (Some(Light(_)), false)
-                    | (Some(Dark(1)), false)
-                    | (Some(Dark(_)), true)
-                    | (Some(Light(1)), true) = (
-                        self.tiles.get(
-                            (i as isize + die as isize * move_dir) as usize,
-                        ),
-                        turn,
-                    ) {
-                        moves.push([
-                            i as u8,
-                            (i as isize + die as isize * move_dir) as u8,
-                        ]);
+                    let target = i as isize + die as isize * move_dir;
+
+                    if (0..24).contains(&target)
+                        && can_land(self.tiles.get(target as usize))
+                    {
+                        moves.push([i as u8, target as u8]);
                     }
                 }
             }
 
             if self.is_all_home()[turn_ind] {
                 let die_pos = if turn {
-                    die as usize
+                    die as usize - 1
                 } else {
-                    23 - die as usize
+                    24 - die as usize
                 };
 
-                if let (Light(_), false) | (Dark(_), true) =
-                    (self.tiles[die_pos], turn)
-                {
+                let own = |t: &Tile| {
+                    matches!((t, turn), (Light(_), false) | (Dark(_), true))
+                };
+
+                if own(&self.tiles[die_pos]) {
                     moves.push([die_pos as u8, SPECIAL_MOVE]);
-                }
+                } else {
+                    // With no checker on the point of the die or further
+                    // from home, the furthest checker may be borne off.
+                    let (further, closer) = if turn {
+                        (&self.tiles[die_pos..18], &self.tiles[..die_pos])
+                    } else {
+                        (&self.tiles[6..=die_pos], &self.tiles[die_pos..])
+                    };
 
-                // At this point if no moves has been pushed,
-                // we can check for 'wasteful' moves.
+                    if !further.iter().any(own) {
+                        let furthest = if turn {
+                            closer.iter().rposition(own)
+                        } else {
+                            closer.iter().position(own).map(|i| die_pos + i)
+                        };
 
-                if moves.is_empty() {
-                    let mut i = die_pos as isize;
-                    while let Some(t) = self.tiles.get(i as usize) {
-                        if let (Light(_), false) | (Dark(_), true) = (t, turn) {
-                            wasteful = true;
+                        if let Some(i) = furthest {
                             moves.push([i as u8, SPECIAL_MOVE]);
-                            break;
                         }
-
-                        i += move_dir;
                     }
                 }
             }
         } else {
-            let off = turn_ind * 18;
-            for i in off..off + 6 {
-                if let (Empty, _) | (Light(_), false) | (Dark(_), true) =
-                    (self.tiles[i], turn)
-                {
-                    moves.push([SPECIAL_MOVE, i as u8]);
-                }
+            let entry = if turn { 24 - die } else { die - 1 };
+
+            if can_land(self.tiles.get(entry as usize)) {
+                moves.push([SPECIAL_MOVE, entry]);
             }
         }
-
-        wasteful
     }
 
     pub fn do_move(&mut self, [from, to]: [u8; 2]) {
@@ -264,47 +263,35 @@ impl GameState {
         }
     }
 
+    // Plays using dice[0] first. `seen_both` gets those using both dice and
+    // `seen_first` those where only dice[0] can be used.
     pub fn get_possible_moves_double_ordered(
         &self,
         turn: bool,
         dice: [u8; 2],
         move_buf: &mut Vec<Vec<[u8; 2]>>,
-        seen: &mut [HashMap<GameState, ArrayVec<[u8; 2], 4>>; 5],
+        seen_both: &mut StateMap,
+        seen_first: &mut StateMap,
     ) {
-        // u for unused
-        // w for wasteful
-        let [seen_ord, seen_1w, seen_2w, seen_1u, seen_1u1w] = seen;
-
         let mut buf1 = move_buf.pop().unwrap_or_default();
         let mut buf2 = move_buf.pop().unwrap_or_default();
 
-        let wasteful1 = self.get_possible_moves(turn, dice[0], &mut buf1);
+        self.get_possible_moves(turn, dice[0], &mut buf1);
 
         for &m1 in &buf1 {
             let mut state = self.clone();
             state.do_move(m1);
 
-            let wasteful2 = self.get_possible_moves(turn, dice[1], &mut buf2);
+            state.get_possible_moves(turn, dice[1], &mut buf2);
 
             if buf2.is_empty() {
-                if wasteful1 {
-                    &mut *seen_1u1w
-                } else {
-                    &mut *seen_1u
-                }
-                .insert(state, [m1].into_iter().collect());
+                seen_first.insert(state, [m1].into_iter().collect());
             } else {
                 for &m2 in &buf2 {
                     let mut state = state.clone();
                     state.do_move(m2);
 
-                    match count_trues(&[wasteful1, wasteful2]) {
-                        0 => &mut *seen_ord,
-                        1 => &mut *seen_1w,
-                        2 => &mut *seen_2w,
-                        _ => unreachable!(),
-                    }
-                    .insert(state, [m1, m2].into_iter().collect());
+                    seen_both.insert(state, [m1, m2].into_iter().collect());
                 }
             }
         }
@@ -313,119 +300,41 @@ impl GameState {
         move_buf.push(buf1);
     }
 
+    // Continues the play `moves` with `die`. `seen[i]` gets the plays where
+    // 4 - i moves can be made.
     fn get_possible_moves_quadruple_ordered(
         &self,
         turn: bool,
         die: u8,
         move_buf: &mut Vec<Vec<[u8; 2]>>,
-        seen: &mut [HashMap<GameState, ArrayVec<[u8; 2], 4>>; 14],
+        moves: &mut ArrayVec<[u8; 2], 4>,
+        seen: &mut [StateMap; 4],
     ) {
-        // u for unused
-        // w for wasteful
-        // transmuting because rustfmt is weird.
-        let tmp: &mut (
-            [StateMap; 5],
-            [StateMap; 4],
-            [StateMap; 3],
-            [StateMap; 2],
-        ) = unsafe { transmute(seen) };
-        let (
-            [seen_ord, seen_1w, seen_2w, seen_3w, seen_4w],
-            [seen_1u, seen_1u1w, seen_1u2w, seen_1u3w],
-            [seen_2u, seen_2u1w, seen_2u2w],
-            [seen_3u, seen_3u1w],
-        ) = tmp;
-
-        let mut buf1 = move_buf.pop().unwrap_or_default();
-        let mut buf2 = move_buf.pop().unwrap_or_default();
-        let mut buf3 = move_buf.pop().unwrap_or_default();
-        let mut buf4 = move_buf.pop().unwrap_or_default();
-
-        let wasteful1 = self.get_possible_moves(turn, die, &mut buf1);
+        if moves.is_full() {
+            seen[0].insert(self.clone(), moves.clone());
+            return;
+        }
 
-        for &m1 in &buf1 {
-            let mut state = self.clone();
-            state.do_move(m1);
+        let mut buf = move_buf.pop().unwrap_or_default();
 
-            let wasteful2 = state.get_possible_moves(turn, die, &mut buf2);
+        self.get_possible_moves(turn, die, &mut buf);
 
-            if buf2.is_empty() {
-                if wasteful1 {
-                    &mut *seen_3u1w
-                } else {
-                    &mut *seen_3u
-                }
-                .insert(state, [m1].into_iter().collect());
-            } else {
-                for &m2 in &buf2 {
-                    let mut state = state.clone();
-                    state.do_move(m2);
+        if buf.is_empty() && !moves.is_empty() {
+            seen[4 - moves.len()].insert(self.clone(), moves.clone());
+        }
 
-                    let wasteful3 =
-                        state.get_possible_moves(turn, die, &mut buf3);
+        for &m in &buf {
+            let mut state = self.clone();
+            state.do_move(m);
 
-                    if buf3.is_empty() {
-                        match count_trues(&[wasteful1, wasteful2]) {
-                            0 => &mut *seen_2u,
-                            1 => &mut *seen_2u1w,
-                            2 => &mut *seen_2u2w,
-                            _ => unreachable!(),
-                        }
-                        .insert(state, [m1, m2].into_iter().collect());
-                    } else {
-                        for &m3 in &buf3 {
-                            let mut state = state.clone();
-                            state.do_move(m3);
-
-                            let wasteful4 =
-                                state.get_possible_moves(turn, die, &mut buf4);
-
-                            if buf4.is_empty() {
-                                match count_trues(&[
-                                    wasteful1, wasteful2, wasteful3,
-                                ]) {
-                                    0 => &mut *seen_1u,
-                                    1 => &mut *seen_1u1w,
-                                    2 => &mut *seen_1u2w,
-                                    3 => &mut *seen_1u3w,
-                                    _ => unreachable!(),
-                                }
-                                .insert(
-                                    state,
-                                    [m1, m2, m3].into_iter().collect(),
-                                );
-                            } else {
-                                for &m4 in &buf4 {
-                                    let mut state = state.clone();
-                                    state.do_move(m4);
-
-                                    match count_trues(&[
-                                        wasteful1, wasteful2, wasteful3,
-                                        wasteful4,
-                                    ]) {
-                                        0 => &mut *seen_ord,
-                                        1 => &mut *seen_1w,
-                                        2 => &mut *seen_2w,
-                                        3 => &mut *seen_3w,
-                                        4 => &mut *seen_4w,
-                                        _ => unreachable!(),
-                                    }
-                                    .insert(
-                                        state,
-                                        [m1, m2, m3, m4].into_iter().collect(),
-                                    );
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            moves.push(m);
+            state.get_possible_moves_quadruple_ordered(
+                turn, die, move_buf, moves, seen,
+            );
+            moves.pop();
         }
 
-        move_buf.push(buf4);
-        move_buf.push(buf3);
-        move_buf.push(buf2);
-        move_buf.push(buf1);
+        move_buf.push(buf);
     }
 
     pub fn get_possible_moves_double(
@@ -440,23 +349,32 @@ impl GameState {
         dice.sort();
         let dice = dice;
 
+        // Plays using the most dice win, and if only one of two different
+        // dice can be used, the larger one.
         if dice[0] != dice[1] {
             let mut seen =
-                [(); 5].map(|()| seen_states_buf.pop().unwrap_or_default());
+                [(); 3].map(|()| seen_states_buf.pop().unwrap_or_default());
 
             for seen in &mut seen {
                 seen.clear();
             }
 
+            let [seen_both, seen_larger, seen_smaller] = &mut seen;
+
             self.get_possible_moves_double_ordered(
-                turn, dice, move_buf, &mut seen,
+                turn,
+                dice,
+                move_buf,
+                seen_both,
+                seen_smaller,
             );
 
             self.get_possible_moves_double_ordered(
                 turn,
                 [dice[1], dice[0]],
                 move_buf,
-                &mut seen,
+                seen_both,
+                seen_larger,
             );
 
             if let Some(seen) = seen.iter_mut().find(|seen| !seen.is_empty()) {
@@ -470,14 +388,18 @@ impl GameState {
             moves.sort();
         } else {
             let mut seen =
-                [(); 14].map(|()| seen_states_buf.pop().unwrap_or_default());
+                [(); 4].map(|()| seen_states_buf.pop().unwrap_or_default());
 
             for seen in &mut seen {
                 seen.clear();
             }
 
             self.get_possible_moves_quadruple_ordered(
-                turn, dice[0], move_buf, &mut seen,
+                turn,
+                dice[0],
+                move_buf,
+                &mut ArrayVec::new(),
+                &mut seen,
             );
 
             if let Some(seen) = seen.iter_mut().find(|seen| !seen.is_empty()) {
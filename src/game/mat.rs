@@ -0,0 +1,495 @@
+//! Matches in the Jellyfish `.mat` text format:
+//!
+//! ```text
+//!  3 point match
+//!
+//!  Game 1
+//!  Alice : 0                       Bob : 0
+//!   1) 31: 8/5 6/5                 52: 13/11 13/8
+//!   2)  Doubles => 2               Drops
+//!       Wins 1 point
+//! ```
+//!
+//! The left column holds Light's actions and the right column Dark's. Points
+//! are numbered from the moving player's side, as in
+//! [`notation`](crate::i8_based::notation).
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::i8_based::{
+    notation::{format_play, parse_play, NotationError},
+    GameState,
+};
+
+use super::{
+    replay::{Replay, ReplayError},
+    Action, GameRecord, Match,
+};
+
+// Entries starting this far into a line are in the right column.
+const RIGHT_COLUMN: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatGame {
+    /// The score before the game, as [light, dark].
+    pub score: [u64; 2],
+    pub actions: Vec<Action>,
+    /// The player credited with the game and the points they won, if the
+    /// file records it.
+    pub winner: Option<(bool, u32)>,
+}
+
+impl MatGame {
+    pub fn final_state(&self) -> GameState {
+        self.actions
+            .iter()
+            .rev()
+            .find_map(|action| match action {
+                Action::Roll(turn) => Some(turn.state),
+                _ => None,
+            })
+            .unwrap_or_else(GameState::new_with_default_setup)
+    }
+}
+
+/// A whole `.mat` file. Parse one with [`str::parse`] and write one with
+/// [`Display`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchFile {
+    /// Zero for money play.
+    pub match_length: u16,
+    /// As [light, dark].
+    pub names: [String; 2],
+    pub games: Vec<MatGame>,
+}
+
+impl MatchFile {
+    /// A match made of `records`, adding up the points of each game for the
+    /// scores.
+    ///
+    /// # Panics
+    ///
+    /// If a game did not start from the default setup.
+    pub fn from_records(
+        match_length: u16,
+        names: [&str; 2],
+        records: &[GameRecord],
+    ) -> Self {
+        let mut score = [0; 2];
+
+        let games = records
+            .iter()
+            .map(|record| {
+                assert_eq!(
+                    record.start,
+                    GameState::new_with_default_setup(),
+                    "Games in a .mat file start from the default setup"
+                );
+
+                let game = MatGame {
+                    score,
+                    actions: record
+                        .turns
+                        .iter()
+                        .cloned()
+                        .map(Action::Roll)
+                        .collect(),
+                    winner: Some((
+                        record.result.winner,
+                        record.result.points(),
+                    )),
+                };
+
+                // Light is index 0.
+                score[(!record.result.winner) as usize] +=
+                    u64::from(record.result.points());
+
+                game
+            })
+            .collect();
+
+        Self {
+            match_length,
+            names: names.map(str::to_owned),
+            games,
+        }
+    }
+}
+
+impl MatchFile {
+    /// The games of `m` so far, with the points credited for each.
+    pub fn from_match(names: [&str; 2], m: &Match) -> Self {
+        let games = m
+            .games
+            .iter()
+            .map(|game| MatGame {
+                score: game.score,
+                actions: game.record.actions.clone(),
+                winner: Some((game.record.winner, game.points)),
+            })
+            .collect();
+
+        Self {
+            match_length: m.rules.length,
+            names: names.map(str::to_owned),
+            games,
+        }
+    }
+}
+
+impl Display for MatchFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, " {} point match", self.match_length)?;
+
+        for (i, game) in self.games.iter().enumerate() {
+            let [light, dark] = &self.names;
+            let [light_score, dark_score] = game.score;
+
+            writeln!(f)?;
+            writeln!(f, " Game {}", i + 1)?;
+            let left = format!("{light} : {light_score}");
+            writeln!(f, " {left:<31} {dark} : {dark_score}")?;
+
+            let mut rows = Vec::new();
+            let mut row = [String::new(), String::new()];
+            let mut state = GameState::new_with_default_setup();
+
+            for action in &game.actions {
+                let entry = match action {
+                    Action::Roll(turn) => {
+                        let [d1, d2] = turn.dice;
+                        let play = format_play(state, turn.turn, &turn.play);
+                        state = turn.state;
+                        format!("{d1}{d2}: {play}").trim_end().to_owned()
+                    }
+                    Action::Double { value, .. } => {
+                        format!(" Doubles => {value}")
+                    }
+                    Action::Take(_) => " Takes".to_owned(),
+                    Action::Drop(_) => " Drops".to_owned(),
+                    Action::Beaver(_) => " Beavers".to_owned(),
+                };
+
+                // Dark's entry always ends a row.
+                if action.player() {
+                    if !row[0].is_empty() {
+                        rows.push(std::mem::take(&mut row));
+                    }
+                    row[0] = entry;
+                } else {
+                    row[1] = entry;
+                    rows.push(std::mem::take(&mut row));
+                }
+            }
+
+            if !row[0].is_empty() {
+                rows.push(row);
+            }
+
+            for (n, [left, right]) in rows.iter().enumerate() {
+                let line = format!("{:3}) {left:<27} {right}", n + 1);
+                writeln!(f, "{}", line.trim_end())?;
+            }
+
+            if let Some((winner, points)) = game.winner {
+                let indent = if winner { 6 } else { 33 };
+                let s = if points == 1 { "" } else { "s" };
+                writeln!(f, "{:indent$}Wins {points} point{s}", "")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a `.mat` file, replaying every play with [`parse_play`] so that
+/// only legal games are accepted.
+impl FromStr for MatchFile {
+    type Err = MatError;
+
+    fn from_str(text: &str) -> Result<Self, MatError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| {
+                let line = line.trim();
+                !line.is_empty() && !line.starts_with(';')
+            });
+
+        let (n, header) =
+            lines.next().ok_or(MatError::new(1, MatErrorKind::Header))?;
+        let match_length = header
+            .trim()
+            .strip_suffix("point match")
+            .and_then(|length| length.trim().parse().ok())
+            .ok_or(MatError::new(n, MatErrorKind::Header))?;
+
+        let mut names = None;
+        let mut games = Vec::new();
+        let mut game: Option<GameReader> = None;
+        // The format has no mark for the Crawford game, so it is taken to be
+        // the first one with a single player within a point of winning.
+        let mut crawford_played = false;
+
+        while let Some((n, line)) = lines.next() {
+            let syntax_err =
+                || MatError::new(n, MatErrorKind::Syntax(line.trim().into()));
+
+            if let Some(number) = line.trim().strip_prefix("Game ") {
+                number.trim().parse::<u32>().map_err(|_| syntax_err())?;
+
+                let (n, line) = lines.next().ok_or_else(syntax_err)?;
+                let (game_names, score) =
+                    parse_score(line).ok_or_else(|| {
+                        MatError::new(
+                            n,
+                            MatErrorKind::Syntax(line.trim().into()),
+                        )
+                    })?;
+
+                let crawford = !crawford_played
+                    && score
+                        .iter()
+                        .filter(|&&s| s + 1 == u64::from(match_length))
+                        .count()
+                        == 1;
+                crawford_played |= crawford;
+
+                names.get_or_insert(game_names);
+                games.extend(game.take().map(|g| g.game));
+                game = Some(GameReader::new(score, crawford));
+                continue;
+            }
+
+            let reader = game.as_mut().ok_or_else(syntax_err)?;
+
+            for (i, (column, tokens)) in entries(line)
+                .ok_or_else(syntax_err)?
+                .into_iter()
+                .enumerate()
+            {
+                let light = i == 0 && column < RIGHT_COLUMN;
+                reader
+                    .read(light, &tokens)
+                    .map_err(|kind| MatError::new(n, kind))?
+                    .ok_or_else(syntax_err)?;
+            }
+        }
+
+        games.extend(game.map(|g| g.game));
+
+        Ok(Self {
+            match_length,
+            names: names.unwrap_or_default(),
+            games,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatError {
+    /// Numbered from 1.
+    pub line: usize,
+    pub kind: MatErrorKind,
+}
+
+impl MatError {
+    fn new(line: usize, kind: MatErrorKind) -> Self {
+        Self { line, kind }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatErrorKind {
+    /// The file does not start with `N point match`.
+    Header,
+    /// A line that could not be parsed.
+    Syntax(String),
+    Play(NotationError),
+    /// A player rolling twice in a row, or doubling when not on roll.
+    OutOfTurn,
+    /// A cube action out of sequence, doubling to the wrong value or
+    /// doubling in the Crawford game.
+    Cube,
+    /// An action after the game has ended.
+    GameOver,
+    /// The game is credited to the player who lost it.
+    Winner,
+}
+
+impl Display for MatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+
+        match &self.kind {
+            MatErrorKind::Header => write!(f, "Expected 'N point match'"),
+            MatErrorKind::Syntax(line) => write!(f, "Could not parse {line:?}"),
+            MatErrorKind::Play(e) => write!(f, "{e}"),
+            MatErrorKind::OutOfTurn => write!(f, "Played out of turn"),
+            MatErrorKind::Cube => write!(f, "Unexpected cube action"),
+            MatErrorKind::GameOver => write!(f, "The game is already over"),
+            MatErrorKind::Winner => write!(f, "Wrong winner"),
+        }
+    }
+}
+
+impl Error for MatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            MatErrorKind::Play(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Builds one game as its lines are read.
+struct GameReader {
+    game: MatGame,
+    replay: Replay,
+}
+
+impl GameReader {
+    fn new(score: [u64; 2], crawford: bool) -> Self {
+        Self {
+            game: MatGame {
+                score,
+                actions: Vec::new(),
+                winner: None,
+            },
+            replay: Replay::new(GameState::new_with_default_setup(), crawford),
+        }
+    }
+
+    // Applies one entry. `Ok(None)` if it is not well formed.
+    fn read(
+        &mut self,
+        player: bool,
+        tokens: &[&str],
+    ) -> Result<Option<()>, MatErrorKind> {
+        let action = match *tokens {
+            ["Doubles", "=>", value] => {
+                let Ok(value) = value.parse() else {
+                    return Ok(None);
+                };
+                self.replay.double(player, value)?
+            }
+            ["Takes"] => self.replay.take(player)?,
+            ["Drops" | "Passes"] => self.replay.drop(player)?,
+            ["Beavers"] => self.replay.beaver(player)?,
+            ["Wins", points, unit, ..] if unit.starts_with("point") => {
+                let Ok(points) = points.parse() else {
+                    return Ok(None);
+                };
+                self.replay.win(player)?;
+                self.game.winner = Some((player, points));
+                return Ok(Some(()));
+            }
+            [roll, ref play @ ..] => {
+                let Some(dice) = parse_roll(roll) else {
+                    return Ok(None);
+                };
+
+                let state = self.replay.state;
+                let (_, new_state) =
+                    parse_play(state, player, dice, &play.join(" "))
+                        .map_err(MatErrorKind::Play)?;
+                self.replay.roll(player, dice, new_state)?
+            }
+            [] => return Ok(None),
+        };
+
+        self.game.actions.push(action);
+        Ok(Some(()))
+    }
+}
+
+impl From<ReplayError> for MatErrorKind {
+    fn from(e: ReplayError) -> Self {
+        match e {
+            ReplayError::OutOfTurn => Self::OutOfTurn,
+            ReplayError::Cube => Self::Cube,
+            ReplayError::GameOver => Self::GameOver,
+            // Plays are checked by `parse_play` first.
+            ReplayError::Illegal => Self::Play(NotationError::Illegal),
+            ReplayError::Winner => Self::Winner,
+        }
+    }
+}
+
+// Splits a line into entries with the column each starts at, skipping the
+// row number. `None` if the line does not start with an entry.
+fn entries(line: &str) -> Option<Vec<(usize, Vec<&str>)>> {
+    let mut entries: Vec<(usize, Vec<&str>)> = Vec::new();
+
+    let mut tokens = tokens(line).peekable();
+    if let Some((_, number)) = tokens.peek() {
+        let row = number.strip_suffix(')').unwrap_or_default();
+        if !row.is_empty() && row.bytes().all(|b| b.is_ascii_digit()) {
+            tokens.next();
+        }
+    }
+
+    for (column, token) in tokens {
+        let starts_entry = parse_roll(token).is_some()
+            || matches!(
+                token,
+                "Doubles" | "Takes" | "Drops" | "Passes" | "Beavers" | "Wins"
+            );
+
+        match entries.last_mut() {
+            Some((_, entry)) if !starts_entry => entry.push(token),
+            None if !starts_entry => return None,
+            _ => entries.push((column, vec![token])),
+        }
+    }
+
+    Some(entries)
+}
+
+// Whitespace separated tokens and the column each starts at.
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut chars = line.char_indices().enumerate().peekable();
+
+    std::iter::from_fn(move || {
+        while chars.next_if(|(_, (_, c))| c.is_whitespace()).is_some() {}
+
+        let &(column, (start, _)) = chars.peek()?;
+        let mut end = line.len();
+        for (_, (i, c)) in chars.by_ref() {
+            if c.is_whitespace() {
+                end = i;
+                break;
+            }
+        }
+
+        Some((column, &line[start..end]))
+    })
+}
+
+// A roll like `31:`.
+fn parse_roll(token: &str) -> Option<[u8; 2]> {
+    match token.as_bytes() {
+        &[d1 @ b'1'..=b'6', d2 @ b'1'..=b'6', b':'] => {
+            Some([d1 - b'0', d2 - b'0'])
+        }
+        _ => None,
+    }
+}
+
+// `Alice : 0     Bob : 3`, with names that may contain spaces.
+fn parse_score(line: &str) -> Option<([String; 2], [u64; 2])> {
+    let mut parts = line.split(" : ");
+    let light = parts.next()?.trim();
+    let (light_score, dark) = parts.next()?.trim().split_once(' ')?;
+    let dark_score = parts.next()?.trim();
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((
+        [light.to_owned(), dark.trim().to_owned()],
+        [light_score.parse().ok()?, dark_score.parse().ok()?],
+    ))
+}
@@ -0,0 +1,184 @@
+use crate::dice::DiceSource;
+
+use super::{play_cubeful_game, CubeRules, CubefulRecord, Player};
+
+/// How a match or money session is played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchRules {
+    /// Zero for a money session, which never ends by itself.
+    pub length: u16,
+    /// No doubling in the game after a player first comes within one point
+    /// of winning. Either player may double again in the games after it.
+    pub crawford: bool,
+    /// Gammons and backgammons only count once the cube has been turned.
+    /// Only applies to money sessions.
+    pub jacoby: bool,
+    /// Only applies to money sessions.
+    pub beavers: bool,
+}
+
+impl MatchRules {
+    /// A match to `length` points with the Crawford rule.
+    pub fn to(length: u16) -> Self {
+        assert!(length > 0, "Use MatchRules::money for money sessions");
+
+        Self {
+            length,
+            crawford: true,
+            jacoby: false,
+            beavers: false,
+        }
+    }
+
+    /// Money play with the Jacoby rule and beavers.
+    pub fn money() -> Self {
+        Self {
+            length: 0,
+            crawford: false,
+            jacoby: true,
+            beavers: true,
+        }
+    }
+}
+
+/// One game of a match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchGame {
+    /// The score before the game, as [light, dark].
+    pub score: [u64; 2],
+    pub crawford: bool,
+    pub record: CubefulRecord,
+    /// The points credited to the winner, after the Jacoby rule.
+    pub points: u32,
+}
+
+/// A match or money session, played one game at a time with
+/// [`play_cubeful_game`]. It is its own record: the score, the Crawford game
+/// and the cube rules of the next game all follow from the games so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Match {
+    pub rules: MatchRules,
+    pub games: Vec<MatchGame>,
+}
+
+impl Match {
+    pub fn new(rules: MatchRules) -> Self {
+        Self {
+            rules,
+            games: Vec::new(),
+        }
+    }
+
+    /// As [light, dark]. Wide enough for any number of games at the
+    /// highest cube.
+    pub fn score(&self) -> [u64; 2] {
+        let mut score = [0; 2];
+        for game in &self.games {
+            // Light is index 0.
+            score[(!game.record.winner) as usize] += u64::from(game.points);
+        }
+        score
+    }
+
+    /// The player who has won the match. Always `None` for money play.
+    pub fn winner(&self) -> Option<bool> {
+        let length = self.rules.length.into();
+        let [light, dark] = self.score();
+
+        if length == 0 {
+            None
+        } else if light >= length {
+            Some(true)
+        } else if dark >= length {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the next game is the Crawford game.
+    pub fn is_crawford(&self) -> bool {
+        self.rules.crawford
+            && self.leader().is_some()
+            && !self.games.iter().any(|game| game.crawford)
+    }
+
+    /// What the cube may do in the next game.
+    pub fn cube_rules(&self) -> CubeRules {
+        let doubling = !self.is_crawford();
+
+        CubeRules {
+            doubling: [doubling; 2],
+            beavers: self.rules.length == 0 && self.rules.beavers,
+        }
+    }
+
+    /// Plays the next game and adds it to the match. The players are given
+    /// as [light, dark].
+    ///
+    /// # Panics
+    ///
+    /// If the match is already over.
+    pub fn play_game(
+        &mut self,
+        players: [&mut dyn Player; 2],
+        dice: &mut dyn DiceSource,
+    ) -> &MatchGame {
+        assert!(self.winner().is_none(), "The match is already over");
+
+        let score = self.score();
+        let crawford = self.is_crawford();
+        let record = play_cubeful_game(players, dice, self.cube_rules());
+
+        let jacoby = self.rules.length == 0 && self.rules.jacoby;
+        let points = if jacoby && record.cube.is_centred() {
+            record.cube.value
+        } else {
+            record.points()
+        };
+
+        self.games.push(MatchGame {
+            score,
+            crawford,
+            record,
+            points,
+        });
+        self.games.last().unwrap()
+    }
+
+    /// Plays games until the match is won, returning the winner.
+    ///
+    /// # Panics
+    ///
+    /// For money sessions, which have no end.
+    pub fn play(
+        &mut self,
+        players: [&mut dyn Player; 2],
+        dice: &mut dyn DiceSource,
+    ) -> bool {
+        assert!(self.rules.length > 0, "Money sessions have no end");
+
+        let [light, dark] = players;
+        loop {
+            if let Some(winner) = self.winner() {
+                return winner;
+            }
+            self.play_game([&mut *light, &mut *dark], dice);
+        }
+    }
+
+    // The only player within one point of winning.
+    fn leader(&self) -> Option<bool> {
+        let length = u64::from(self.rules.length);
+        let [light, dark] = self.score().map(|s| s + 1 == length);
+
+        match (light, dark) {
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            _ => None,
+        }
+    }
+}
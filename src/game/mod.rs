@@ -0,0 +1,306 @@
+use crate::{
+    backgammon::{Backgammon, GameResult},
+    cube::{Cube, CubeResponse},
+    dice::DiceSource,
+    i8_based::{GameState, MoveBuffer, Play},
+};
+
+pub use match_play::{Match, MatchGame, MatchRules};
+pub use player::{
+    EvaluatorPlayer, HumanPlayer, PipCountPlayer, Player, RandomPlayer,
+};
+pub use repl::Repl;
+
+pub mod mat;
+mod match_play;
+mod player;
+mod repl;
+mod replay;
+pub mod sgf;
+
+/// One played turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Turn {
+    pub turn: bool,
+    pub dice: [u8; 2],
+    pub play: Play,
+    /// The position after the turn was played.
+    pub state: GameState,
+}
+
+/// Something a player does in a recorded game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Action {
+    Roll(Turn),
+    /// `player` offers the cube at `value`.
+    Double {
+        player: bool,
+        value: u32,
+    },
+    Take(bool),
+    Drop(bool),
+    /// Takes the double and redoubles at once, keeping the cube.
+    Beaver(bool),
+}
+
+impl Action {
+    /// The player taking the action.
+    pub fn player(&self) -> bool {
+        match *self {
+            Self::Roll(Turn { turn, .. }) => turn,
+            Self::Double { player, .. } => player,
+            Self::Take(player) | Self::Drop(player) | Self::Beaver(player) => {
+                player
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    pub start: GameState,
+    pub turns: Vec<Turn>,
+    pub result: GameResult,
+}
+
+impl GameRecord {
+    pub fn final_state(&self) -> GameState {
+        self.turns.last().map_or(self.start, |t| t.state)
+    }
+}
+
+/// Which cube actions a game allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubeRules {
+    /// Which players may turn the cube, as [light, dark].
+    pub doubling: [bool; 2],
+    pub beavers: bool,
+}
+
+impl Default for CubeRules {
+    /// Doubling without beavers.
+    fn default() -> Self {
+        Self {
+            doubling: [true, true],
+            beavers: false,
+        }
+    }
+}
+
+impl CubeRules {
+    /// Doubling with beavers, as usual for money play.
+    pub fn money() -> Self {
+        Self {
+            doubling: [true, true],
+            beavers: true,
+        }
+    }
+}
+
+/// A game played with the cube.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubefulRecord {
+    pub start: GameState,
+    pub actions: Vec<Action>,
+    /// The cube at the end of the game. A dropped double leaves it at the
+    /// value before the double.
+    pub cube: Cube,
+    pub winner: bool,
+    /// How the game ended on the board. `None` if a double was dropped.
+    pub result: Option<GameResult>,
+}
+
+impl CubefulRecord {
+    pub fn final_state(&self) -> GameState {
+        self.actions
+            .iter()
+            .rev()
+            .find_map(|action| match action {
+                Action::Roll(turn) => Some(turn.state),
+                _ => None,
+            })
+            .unwrap_or(self.start)
+    }
+
+    /// The points won: the cube value, times the value of the win if it was
+    /// played out.
+    pub fn points(&self) -> u32 {
+        self.cube.value * self.result.map_or(1, |result| result.points())
+    }
+}
+
+/// Each side rolls one die, re-rolling ties. The higher die starts and plays
+/// both dice. Returns the starting player and the dice as [light, dark].
+pub fn roll_opening(dice: &mut dyn DiceSource) -> (bool, [u8; 2]) {
+    loop {
+        let [light, dark] = dice.roll();
+        if light != dark {
+            return (light > dark, [light, dark]);
+        }
+    }
+}
+
+/// Plays a game from the default setup until one side has borne off. The
+/// players are given as [light, dark].
+///
+/// The game is fully determined by the players and the dice, so replaying
+/// with e.g. [`SeededDice`](crate::dice::SeededDice) of the same seed gives
+/// the same game for deterministic players.
+pub fn play_game(
+    players: [&mut dyn Player; 2],
+    dice: &mut dyn DiceSource,
+) -> GameRecord {
+    play_game_from(GameState::new_with_default_setup(), players, dice)
+}
+
+/// Like [`play_game`], but starting from `start` instead of the default
+/// setup.
+///
+/// # Panics
+///
+/// If `start` is already finished or a player chooses a position that is not
+/// reachable with its roll.
+pub fn play_game_from(
+    start: GameState,
+    players: [&mut dyn Player; 2],
+    dice: &mut dyn DiceSource,
+) -> GameRecord {
+    assert!(start.result().is_none(), "The game is already over");
+
+    let mut moves = MoveBuffer::new();
+    let mut turns = Vec::new();
+
+    let mut state = start;
+    let (mut turn, mut roll) = roll_opening(dice);
+
+    loop {
+        moves.generate(turn, state, roll);
+
+        let new_state = players[(!turn) as usize].choose_move(&moves);
+        let (play, _) = moves
+            .play_iterator()
+            .find(|&(_, s)| s == new_state)
+            .unwrap_or_else(|| {
+                panic!("Player chose an unreachable position:\n{new_state}")
+            });
+
+        state = new_state;
+        turns.push(Turn {
+            turn,
+            dice: roll,
+            play,
+            state,
+        });
+
+        if let Some(result) = state.result() {
+            return GameRecord {
+                start,
+                turns,
+                result,
+            };
+        }
+
+        turn = !turn;
+        roll = dice.roll();
+    }
+}
+
+/// Plays a game from the default setup with the cube, following `rules`. The
+/// players are given as [light, dark].
+///
+/// Before each roll after the opening one, a player who may double is asked
+/// with [`Player::offer_double`], and the opponent answers with
+/// [`Player::answer_double`]. A beaver when `rules` does not allow them, or
+/// that would pass [`Cube::MAX_VALUE`], is taken as a take.
+pub fn play_cubeful_game(
+    mut players: [&mut dyn Player; 2],
+    dice: &mut dyn DiceSource,
+    rules: CubeRules,
+) -> CubefulRecord {
+    let start = GameState::new_with_default_setup();
+
+    let mut moves = MoveBuffer::new();
+    let mut actions = Vec::new();
+    let mut cube = Cube::new();
+
+    let mut state = start;
+    let (mut turn, opening) = roll_opening(dice);
+    let mut opening = Some(opening);
+
+    loop {
+        let [light, dark] = &mut players;
+        let [player, opponent] =
+            if turn { [light, dark] } else { [dark, light] };
+
+        // Light is index 0.
+        if rules.doubling[(!turn) as usize]
+            && !actions.is_empty()
+            && cube.may_double(turn)
+            && player.offer_double(state, turn, cube)
+        {
+            actions.push(Action::Double {
+                player: turn,
+                value: cube.value * 2,
+            });
+
+            match opponent.answer_double(state, !turn, cube, rules.beavers) {
+                CubeResponse::Drop => {
+                    actions.push(Action::Drop(!turn));
+                    return CubefulRecord {
+                        start,
+                        actions,
+                        cube,
+                        winner: turn,
+                        result: None,
+                    };
+                }
+                CubeResponse::Beaver if rules.beavers && cube.may_beaver() => {
+                    actions.push(Action::Beaver(!turn));
+                    cube = cube.beavered(!turn);
+                }
+                _ => {
+                    actions.push(Action::Take(!turn));
+                    cube = cube.taken(!turn);
+                }
+            }
+        }
+
+        // Rolled after the cube action, so a dropped double draws no dice.
+        let roll = opening.take().unwrap_or_else(|| dice.roll());
+        moves.generate(turn, state, roll);
+
+        let new_state = player.choose_move(&moves);
+        let (play, _) = moves
+            .play_iterator()
+            .find(|&(_, s)| s == new_state)
+            .unwrap_or_else(|| {
+                panic!("Player chose an unreachable position:\n{new_state}")
+            });
+
+        state = new_state;
+        actions.push(Action::Roll(Turn {
+            turn,
+            dice: roll,
+            play,
+            state,
+        }));
+
+        if let Some(result) = state.result() {
+            return CubefulRecord {
+                start,
+                actions,
+                cube,
+                winner: result.winner,
+                result: Some(result),
+            };
+        }
+
+        turn = !turn;
+    }
+}
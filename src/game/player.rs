@@ -0,0 +1,300 @@
+use std::io::{self, BufRead, StdinLock, Stdout, Write};
+
+use crate::{
+    backgammon::Backgammon,
+    cube::{Cube, CubeResponse},
+    i8_based::{
+        notation::format_play, Evaluator, GameState, MoveBuffer, PipCount,
+        StaticEval,
+    },
+    render::{Renderer, Theme},
+    rng::Rng,
+};
+
+/// Something that picks a play for a roll.
+pub trait Player {
+    /// Returns one of the positions yielded by `moves.state_iterator()`.
+    fn choose_move(&mut self, moves: &MoveBuffer) -> GameState;
+
+    /// Whether `turn` doubles `cube` before rolling in `state`. Only asked
+    /// when `turn` may double. Never doubles by default.
+    fn offer_double(
+        &mut self,
+        _state: GameState,
+        _turn: bool,
+        _cube: Cube,
+    ) -> bool {
+        false
+    }
+
+    /// How `turn` answers a double of `cube` by the opponent, who is about
+    /// to roll in `state`. [`CubeResponse::Beaver`] is only allowed if
+    /// `beavers` is set. Takes by default.
+    fn answer_double(
+        &mut self,
+        _state: GameState,
+        _turn: bool,
+        _cube: Cube,
+        _beavers: bool,
+    ) -> CubeResponse {
+        CubeResponse::Take
+    }
+}
+
+/// Picks uniformly among the legal plays.
+pub struct RandomPlayer {
+    rng: Rng,
+}
+
+impl RandomPlayer {
+    pub fn new(rng: Rng) -> Self {
+        Self { rng }
+    }
+}
+
+impl Player for RandomPlayer {
+    fn choose_move(&mut self, moves: &MoveBuffer) -> GameState {
+        let n = moves.state_iterator().count();
+        moves.state_iterator().nth(self.rng.below(n)).unwrap()
+    }
+}
+
+/// Picks the play that leaves it furthest ahead in the race, counting hit
+/// checkers as sent back to the start. Ties go to the play leaving the fewest
+/// blots.
+#[derive(Default)]
+pub struct PipCountPlayer;
+
+impl Player for PipCountPlayer {
+    fn choose_move(&mut self, moves: &MoveBuffer) -> GameState {
+        let turn = moves.turn();
+
+        moves
+            .state_iterator()
+            .min_by_key(|s| {
+                let [light, dark] = s.get_tot_dist();
+                let net = light as i32 - dark as i32;
+                let blots = (0..24)
+                    .filter(|&i| s.checkers(i) == if turn { 1 } else { -1 })
+                    .count();
+
+                (if turn { net } else { -net }, blots)
+            })
+            .unwrap()
+    }
+}
+
+/// Picks the play [`Evaluator::get_best_move`] finds searching `depth` plies,
+/// and makes cube decisions with [`Evaluator::get_cubeful_eval`] one ply
+/// shallower.
+pub struct EvaluatorPlayer<E = PipCount> {
+    evaluator: Evaluator<E>,
+    buffer: MoveBuffer,
+    depth: u32,
+}
+
+impl EvaluatorPlayer {
+    /// `depth` counts the ply being chosen, so it must be at least 1.
+    pub fn new(depth: u32) -> Self {
+        Self::with_eval(depth, PipCount)
+    }
+}
+
+impl<E: StaticEval> EvaluatorPlayer<E> {
+    /// Like [`new`](EvaluatorPlayer::new), scoring the leaves of the search
+    /// with `static_eval`.
+    pub fn with_eval(depth: u32, static_eval: E) -> Self {
+        assert!(depth > 0, "Depth must be at least 1");
+
+        Self {
+            evaluator: Evaluator::with_eval(static_eval),
+            buffer: MoveBuffer::new(),
+            depth,
+        }
+    }
+}
+
+impl<E: StaticEval> Player for EvaluatorPlayer<E> {
+    fn choose_move(&mut self, moves: &MoveBuffer) -> GameState {
+        self.evaluator
+            .get_best_move(
+                moves.state(),
+                moves.turn(),
+                moves.dice(),
+                self.depth,
+                &mut self.buffer,
+            )
+            .map(|(_, state, _)| state)
+            .unwrap()
+    }
+
+    fn offer_double(
+        &mut self,
+        state: GameState,
+        turn: bool,
+        cube: Cube,
+    ) -> bool {
+        self.evaluator
+            .get_cubeful_eval(state, turn, cube, self.depth - 1)
+            .should_double()
+    }
+
+    fn answer_double(
+        &mut self,
+        state: GameState,
+        turn: bool,
+        cube: Cube,
+        beavers: bool,
+    ) -> CubeResponse {
+        self.evaluator
+            .get_cubeful_eval(state, !turn, cube, self.depth - 1)
+            .response(beavers)
+    }
+}
+
+/// Asks a person to pick from a numbered list of plays, showing the board
+/// from the side of the player on roll.
+pub struct HumanPlayer<R, W> {
+    input: R,
+    output: W,
+    theme: Theme,
+}
+
+impl HumanPlayer<StdinLock<'static>, Stdout> {
+    pub fn stdio() -> Self {
+        Self::new(io::stdin().lock(), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> HumanPlayer<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self {
+            input,
+            output,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn prompt(&mut self, moves: &MoveBuffer) -> io::Result<GameState> {
+        let state = moves.state();
+        let options: Vec<_> = moves.play_iterator().collect();
+
+        let player = name(moves.turn());
+        let board = Renderer::new(self.theme, moves.turn()).render(&state);
+        writeln!(self.output, "{board}")?;
+        writeln!(self.output, "{player} rolled {:?}", moves.dice())?;
+
+        for (i, (play, _)) in options.iter().enumerate() {
+            let text = match format_play(state, moves.turn(), play) {
+                text if text.is_empty() => "no move".to_owned(),
+                text => text,
+            };
+            writeln!(self.output, "{i:3}: {text}")?;
+        }
+
+        let mut line = String::new();
+        loop {
+            write!(self.output, "Choose a play: ")?;
+            self.output.flush()?;
+
+            line.clear();
+            if self.input.read_line(&mut line)? == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+
+            match line.trim().parse::<usize>() {
+                Ok(i) if i < options.len() => return Ok(options[i].1),
+                _ => writeln!(
+                    self.output,
+                    "Enter a number from 0 to {}",
+                    options.len() - 1
+                )?,
+            }
+        }
+    }
+
+    // Shows the board from `turn`'s side and reads lines until one is among
+    // `answers`, returning its index.
+    fn ask(
+        &mut self,
+        state: GameState,
+        turn: bool,
+        question: &str,
+        answers: &[&str],
+    ) -> io::Result<usize> {
+        let board = Renderer::new(self.theme, turn).render(&state);
+        writeln!(self.output, "{board}")?;
+
+        let mut line = String::new();
+        loop {
+            write!(self.output, "{question} ({}) ", answers.join("/"))?;
+            self.output.flush()?;
+
+            line.clear();
+            if self.input.read_line(&mut line)? == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+
+            let answer = line.trim().to_ascii_lowercase();
+            if let Some(i) = answers.iter().position(|&a| a == answer) {
+                return Ok(i);
+            }
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> Player for HumanPlayer<R, W> {
+    fn choose_move(&mut self, moves: &MoveBuffer) -> GameState {
+        self.prompt(moves).expect("Failed to read play")
+    }
+
+    fn offer_double(
+        &mut self,
+        state: GameState,
+        turn: bool,
+        cube: Cube,
+    ) -> bool {
+        let question = format!("{} doubles to {}?", name(turn), cube.value * 2);
+        self.ask(state, turn, &question, &["y", "n"])
+            .expect("Failed to read answer")
+            == 0
+    }
+
+    fn answer_double(
+        &mut self,
+        state: GameState,
+        turn: bool,
+        cube: Cube,
+        beavers: bool,
+    ) -> CubeResponse {
+        let question =
+            format!("{} doubles to {}.", name(!turn), cube.value * 2);
+        let answers: &[_] = if beavers {
+            &["take", "drop", "beaver"]
+        } else {
+            &["take", "drop"]
+        };
+
+        match self
+            .ask(state, turn, &question, answers)
+            .expect("Failed to read answer")
+        {
+            0 => CubeResponse::Take,
+            1 => CubeResponse::Drop,
+            _ => CubeResponse::Beaver,
+        }
+    }
+}
+
+pub(crate) fn name(player: bool) -> &'static str {
+    if player {
+        "Light"
+    } else {
+        "Dark"
+    }
+}
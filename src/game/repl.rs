@@ -0,0 +1,273 @@
+use std::io::{self, BufRead, StdinLock, Stdout, Write};
+
+use crate::{
+    backgammon::Backgammon,
+    dice::DiceSource,
+    i8_based::{
+        notation::{format_play, parse_play},
+        Evaluator, GameState, MoveBuffer, Play,
+    },
+    render::{Renderer, Theme},
+};
+
+use super::{player::name, roll_opening, GameRecord, Turn};
+
+// Plays listed by `hint`.
+const HINTS: usize = 3;
+
+const HELP: &str = "\
+Enter a play in standard notation, e.g. 13/9 6/5* or bar/22(2), or one of:
+  hint   list the engine's best plays
+  undo   take back your last play
+  board  show the board again
+  help   show this message
+  quit   leave the game";
+
+/// Plays a game between a person typing moves and [`Evaluator`], for checking
+/// the engine's play by hand.
+pub struct Repl<R, W> {
+    input: R,
+    output: W,
+    theme: Theme,
+    human: bool,
+    depth: u32,
+    evaluator: Evaluator,
+    moves: MoveBuffer,
+}
+
+impl Repl<StdinLock<'static>, Stdout> {
+    pub fn stdio() -> Self {
+        Self::new(io::stdin().lock(), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> Repl<R, W> {
+    /// The person plays Light against a 2 ply search.
+    pub fn new(input: R, output: W) -> Self {
+        Self {
+            input,
+            output,
+            theme: Theme::default(),
+            human: true,
+            depth: 2,
+            evaluator: Evaluator::new(),
+            moves: MoveBuffer::new(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The side the person plays.
+    pub fn with_side(mut self, human: bool) -> Self {
+        self.human = human;
+        self
+    }
+
+    /// How many plies the engine searches, for its own plays and for hints.
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        assert!(depth > 0, "Depth must be at least 1");
+        self.depth = depth;
+        self
+    }
+
+    /// Plays a game from the default setup. Returns `None` if the person
+    /// quits or the input ends before the game is over.
+    pub fn play(
+        &mut self,
+        dice: &mut dyn DiceSource,
+    ) -> io::Result<Option<GameRecord>> {
+        let start = GameState::new_with_default_setup();
+        let mut turns: Vec<Turn> = Vec::new();
+
+        let (mut turn, mut roll) = roll_opening(dice);
+        writeln!(
+            self.output,
+            "Light rolls {}, Dark rolls {}: {} starts",
+            roll[0],
+            roll[1],
+            name(turn)
+        )?;
+
+        loop {
+            let state = turns.last().map_or(start, |t| t.state);
+
+            if let Some(result) = state.result() {
+                writeln!(self.output, "{}", self.board(state))?;
+                writeln!(
+                    self.output,
+                    "{} wins {} point(s)",
+                    name(result.winner),
+                    result.points()
+                )?;
+                return Ok(Some(GameRecord {
+                    start,
+                    turns,
+                    result,
+                }));
+            }
+
+            let play = if turn == self.human {
+                match self.prompt(state, roll)? {
+                    Command::Play(play) => play,
+                    Command::Undo => {
+                        // Back to before the person's previous play, with the
+                        // same dice.
+                        let last = turns.iter().rposition(|t| t.turn == turn);
+                        match last {
+                            Some(i) => {
+                                roll = turns[i].dice;
+                                turns.truncate(i);
+                            }
+                            None => writeln!(self.output, "Nothing to undo")?,
+                        }
+                        continue;
+                    }
+                    Command::Quit => return Ok(None),
+                }
+            } else {
+                let (play, _, _) = self
+                    .evaluator
+                    .get_best_move(
+                        state,
+                        turn,
+                        roll,
+                        self.depth,
+                        &mut self.moves,
+                    )
+                    .unwrap();
+                writeln!(
+                    self.output,
+                    "{} rolls {}-{} and plays {}",
+                    name(turn),
+                    roll[0],
+                    roll[1],
+                    describe(state, turn, &play)
+                )?;
+                play
+            };
+
+            let new_state = play.iter().fold(state, |s, &[from, die]| {
+                s.do_move(turn, from, die).unwrap()
+            });
+            turns.push(Turn {
+                turn,
+                dice: roll,
+                play,
+                state: new_state,
+            });
+
+            turn = !turn;
+            roll = dice.roll();
+        }
+    }
+
+    fn board(&self, state: GameState) -> String {
+        Renderer::new(self.theme, self.human).render(&state)
+    }
+
+    fn prompt(
+        &mut self,
+        state: GameState,
+        dice: [u8; 2],
+    ) -> io::Result<Command> {
+        let turn = self.human;
+        self.moves.generate(turn, state, dice);
+
+        writeln!(self.output, "{}", self.board(state))?;
+
+        // A roll with no legal moves is passed without asking.
+        let plays: Vec<_> = self.moves.play_iterator().collect();
+        if let [(play, _)] = &plays[..] {
+            if play.is_empty() {
+                writeln!(
+                    self.output,
+                    "{} rolls {}-{} and cannot move",
+                    name(turn),
+                    dice[0],
+                    dice[1]
+                )?;
+                return Ok(Command::Play(Play::new()));
+            }
+        }
+
+        let mut line = String::new();
+        loop {
+            write!(
+                self.output,
+                "{} to play {}-{}: ",
+                name(turn),
+                dice[0],
+                dice[1]
+            )?;
+            self.output.flush()?;
+
+            line.clear();
+            if self.input.read_line(&mut line)? == 0 {
+                writeln!(self.output)?;
+                return Ok(Command::Quit);
+            }
+
+            match line.trim().to_ascii_lowercase().as_str() {
+                "" => {}
+                "help" | "?" => writeln!(self.output, "{HELP}")?,
+                "board" => writeln!(self.output, "{}", self.board(state))?,
+                "hint" => self.hint(state, dice)?,
+                "undo" => return Ok(Command::Undo),
+                "quit" | "exit" => return Ok(Command::Quit),
+                text => match parse_play(state, turn, dice, text) {
+                    Ok((play, _)) => return Ok(Command::Play(play)),
+                    Err(e) => writeln!(self.output, "{e}")?,
+                },
+            }
+        }
+    }
+
+    /// Lists the best plays with their evaluations from the person's side:
+    /// their chances and cubeless equity.
+    fn hint(&mut self, state: GameState, dice: [u8; 2]) -> io::Result<()> {
+        let turn = self.human;
+        self.moves.generate(turn, state, dice);
+
+        let mut plays: Vec<_> = self
+            .moves
+            .play_iterator()
+            .map(|(play, new_state)| {
+                let eval = self
+                    .evaluator
+                    .get_brute_force_eval(new_state, !turn, self.depth - 1)
+                    .flip();
+                (play, eval)
+            })
+            .collect();
+        plays.sort_by(|(_, a), (_, b)| {
+            b.cubeless_equity().total_cmp(&a.cubeless_equity())
+        });
+
+        for (i, (play, eval)) in plays.iter().take(HINTS).enumerate() {
+            writeln!(
+                self.output,
+                "{:3}. {:<24} {eval}",
+                i + 1,
+                describe(state, turn, play)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+enum Command {
+    Play(Play),
+    Undo,
+    Quit,
+}
+
+fn describe(state: GameState, turn: bool, play: &[[u8; 2]]) -> String {
+    match format_play(state, turn, play) {
+        text if text.is_empty() => "no move".to_owned(),
+        text => text,
+    }
+}
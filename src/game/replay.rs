@@ -0,0 +1,170 @@
+use crate::{
+    backgammon::Backgammon,
+    cube::Cube,
+    i8_based::{GameState, MoveBuffer},
+};
+
+use super::{Action, Turn};
+
+// Why an action does not fit the game so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReplayError {
+    OutOfTurn,
+    Cube,
+    GameOver,
+    /// The position cannot be reached with the roll.
+    Illegal,
+    /// The game is credited to the player who lost it.
+    Winner,
+}
+
+// Follows a recorded game one action at a time, checking each against the
+// rules as far as the record allows.
+pub(crate) struct Replay {
+    pub(crate) state: GameState,
+    moves: MoveBuffer,
+    last_roll: Option<bool>,
+    cube: Cube,
+    // Whether this is the Crawford game, which has no doubling.
+    crawford: bool,
+    // The player whose double is waiting for an answer.
+    offered: Option<bool>,
+    // The winner once the game is decided on the board or by a drop.
+    decided: Option<bool>,
+    credited: bool,
+}
+
+impl Replay {
+    pub(crate) fn new(start: GameState, crawford: bool) -> Self {
+        Self {
+            state: start,
+            moves: MoveBuffer::new(),
+            last_roll: None,
+            cube: Cube::new(),
+            crawford,
+            offered: None,
+            decided: None,
+            credited: false,
+        }
+    }
+
+    pub(crate) fn cube(&self) -> Cube {
+        self.cube
+    }
+
+    pub(crate) fn roll(
+        &mut self,
+        player: bool,
+        dice: [u8; 2],
+        state: GameState,
+    ) -> Result<Action, ReplayError> {
+        self.check_open()?;
+        if self.offered.is_some() {
+            return Err(ReplayError::Cube);
+        }
+        if self.last_roll == Some(player) {
+            return Err(ReplayError::OutOfTurn);
+        }
+
+        // Record the play the move generator gives for the position, as
+        // `play_game` does.
+        self.moves.generate(player, self.state, dice);
+        let (play, _) = self
+            .moves
+            .play_iterator()
+            .find(|&(_, s)| s == state)
+            .ok_or(ReplayError::Illegal)?;
+
+        self.state = state;
+        self.last_roll = Some(player);
+        self.decided = state.result().map(|result| result.winner);
+
+        Ok(Action::Roll(Turn {
+            turn: player,
+            dice,
+            play,
+            state,
+        }))
+    }
+
+    pub(crate) fn double(
+        &mut self,
+        player: bool,
+        value: u32,
+    ) -> Result<Action, ReplayError> {
+        self.check_open()?;
+        // Only the player about to roll may double, and never before the
+        // opening roll.
+        if self.offered.is_none()
+            && self.last_roll.is_none_or(|last| last == player)
+        {
+            return Err(ReplayError::OutOfTurn);
+        }
+        if self.crawford
+            || self.offered.is_some()
+            || !self.cube.may_double(player)
+            || value != self.cube.value * 2
+        {
+            return Err(ReplayError::Cube);
+        }
+
+        self.offered = Some(player);
+        Ok(Action::Double { player, value })
+    }
+
+    pub(crate) fn take(&mut self, player: bool) -> Result<Action, ReplayError> {
+        self.answer(player)?;
+        self.cube = self.cube.taken(player);
+        Ok(Action::Take(player))
+    }
+
+    pub(crate) fn beaver(
+        &mut self,
+        player: bool,
+    ) -> Result<Action, ReplayError> {
+        if !self.cube.may_beaver() {
+            return Err(ReplayError::Cube);
+        }
+
+        self.answer(player)?;
+        self.cube = self.cube.beavered(player);
+        Ok(Action::Beaver(player))
+    }
+
+    pub(crate) fn drop(&mut self, player: bool) -> Result<Action, ReplayError> {
+        self.answer(player)?;
+        self.decided = Some(!player);
+        Ok(Action::Drop(player))
+    }
+
+    // Credits the game to `player`.
+    pub(crate) fn win(&mut self, player: bool) -> Result<(), ReplayError> {
+        if self.credited {
+            return Err(ReplayError::GameOver);
+        }
+        if self.decided.is_some_and(|winner| winner != player) {
+            return Err(ReplayError::Winner);
+        }
+
+        self.credited = true;
+        Ok(())
+    }
+
+    fn answer(&mut self, player: bool) -> Result<(), ReplayError> {
+        self.check_open()?;
+        if self.offered != Some(!player) {
+            return Err(ReplayError::Cube);
+        }
+
+        self.offered = None;
+        Ok(())
+    }
+
+    fn check_open(&self) -> Result<(), ReplayError> {
+        if self.credited || self.decided.is_some() {
+            return Err(ReplayError::GameOver);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,715 @@
+//! Game records in the Smart Game Format as written by GNU Backgammon, e.g.
+//!
+//! ```text
+//! (;FF[4]GM[6]MI[length:3][game:0][ws:0][bs:0]PW[Alice]PB[Bob]RE[W+1]
+//! ;W[31qtst]C[The best opening roll]
+//! ;B[52mkmh]
+//! ;W[double];B[drop])
+//! ```
+//!
+//! Points are the letters `a` to `x` for the indices 0 to 23 of
+//! [`GameState`], with `y` for the bar and `z` for off. `W` is Light and `B`
+//! is Dark. Only the main line of each game is kept, and properties other
+//! than the ones above, the setup properties `AE`, `AW` and `AB`, the
+//! comments `C` and `RU[Crawford:CrawfordGame]` marking the Crawford game are
+//! skipped.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    iter::Peekable,
+    str::{CharIndices, FromStr},
+};
+
+use crate::{
+    backgammon::Backgammon,
+    i8_based::{GameState, MoveError, SPECIAL_MOVE},
+};
+
+use super::{
+    replay::{Replay, ReplayError},
+    Action, GameRecord,
+};
+
+const BAR: char = 'y';
+const OFF: char = 'z';
+
+/// An action with the comment on its node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SgfMove {
+    pub action: Action,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SgfGame {
+    /// As [light, dark].
+    pub names: [String; 2],
+    /// Zero for money play.
+    pub match_length: u16,
+    /// Counting from 0.
+    pub game_number: u32,
+    /// The score before the game, as [light, dark].
+    pub score: [u16; 2],
+    /// Whether this is the Crawford game, which has no doubling.
+    pub crawford: bool,
+    pub start: GameState,
+    /// The comment on the root node.
+    pub comment: Option<String>,
+    pub moves: Vec<SgfMove>,
+    /// The player credited with the game and the points they won.
+    pub winner: Option<(bool, u32)>,
+}
+
+impl SgfGame {
+    /// A money game made of `record`, without comments.
+    pub fn from_record(names: [&str; 2], record: &GameRecord) -> Self {
+        Self {
+            names: names.map(str::to_owned),
+            match_length: 0,
+            game_number: 0,
+            score: [0, 0],
+            crawford: false,
+            start: record.start,
+            comment: None,
+            moves: record
+                .turns
+                .iter()
+                .map(|turn| SgfMove {
+                    action: Action::Roll(turn.clone()),
+                    comment: None,
+                })
+                .collect(),
+            winner: Some((record.result.winner, record.result.points())),
+        }
+    }
+
+    pub fn final_state(&self) -> GameState {
+        self.moves
+            .iter()
+            .rev()
+            .find_map(|m| match &m.action {
+                Action::Roll(turn) => Some(turn.state),
+                _ => None,
+            })
+            .unwrap_or(self.start)
+    }
+
+    // Interprets the main line of one game tree.
+    fn from_nodes(nodes: &[Node]) -> Result<Self, (usize, SgfErrorKind)> {
+        let mut game = Self {
+            names: Default::default(),
+            match_length: 0,
+            game_number: 0,
+            score: [0, 0],
+            crawford: false,
+            start: GameState::new_with_default_setup(),
+            comment: None,
+            moves: Vec::new(),
+            winner: None,
+        };
+        let mut replay = None;
+
+        for (i, node) in nodes.iter().enumerate() {
+            game.read_node(node, &mut replay)
+                .map_err(|kind| (i, kind))?;
+        }
+
+        // `RE` is on the root, so the winner is checked once the moves are
+        // known.
+        if let (Some(replay), Some((winner, _))) = (&mut replay, game.winner) {
+            replay.win(winner).map_err(|e| (0, e.into()))?;
+        }
+
+        Ok(game)
+    }
+
+    // `replay` is started by the first move, after which the position can no
+    // longer be set up.
+    fn read_node(
+        &mut self,
+        node: &Node,
+        replay: &mut Option<Replay>,
+    ) -> Result<(), SgfErrorKind> {
+        let mut comment = None;
+
+        for (property, values) in node {
+            let value_err = |value: &str| SgfErrorKind::Value {
+                property: property.clone(),
+                value: value.to_owned(),
+            };
+            let single = || match values.as_slice() {
+                [value] => Ok(value.as_str()),
+                _ => Err(value_err(&values.join("]["))),
+            };
+
+            match property.as_str() {
+                "GM" if single()? != "6" => {
+                    return Err(SgfErrorKind::NotBackgammon)
+                }
+                "PW" => self.names[0] = single()?.to_owned(),
+                "PB" => self.names[1] = single()?.to_owned(),
+                "C" => comment = Some(single()?.to_owned()),
+                "MI" => {
+                    for value in values {
+                        let (key, n) = value
+                            .split_once(':')
+                            .ok_or_else(|| value_err(value))?;
+                        let n =
+                            || n.parse::<u16>().map_err(|_| value_err(value));
+
+                        match key {
+                            "length" => self.match_length = n()?,
+                            "game" => self.game_number = n()?.into(),
+                            "ws" => self.score[0] = n()?,
+                            "bs" => self.score[1] = n()?,
+                            _ => {}
+                        }
+                    }
+                }
+                "RU" => {
+                    self.crawford = values.iter().any(|value| {
+                        value.split(':').any(|rule| rule == "CrawfordGame")
+                    });
+                }
+                "RE" => {
+                    let value = single()?;
+                    self.winner =
+                        parse_result(value).ok_or_else(|| value_err(value))?;
+                }
+                "AE" | "AW" | "AB" if replay.is_some() => {
+                    return Err(SgfErrorKind::Setup)
+                }
+                "AE" | "AW" | "AB" => {
+                    self.start = set_up(self.start, property, values).map_err(
+                        |value| value_err(value.unwrap_or_default()),
+                    )?;
+                }
+                "W" | "B" => {
+                    let player = property == "W";
+                    let value = single()?;
+
+                    let (start, crawford) = (self.start, self.crawford);
+                    let replay = replay
+                        .get_or_insert_with(|| Replay::new(start, crawford));
+
+                    let action = match value {
+                        "double" => {
+                            replay.double(player, replay.cube().value * 2)?
+                        }
+                        "take" => replay.take(player)?,
+                        "drop" => replay.drop(player)?,
+                        "beaver" => replay.beaver(player)?,
+                        _ => {
+                            let (dice, steps) = parse_roll(value)
+                                .ok_or_else(|| value_err(value))?;
+                            let state =
+                                play_steps(replay.state, player, &steps)?;
+                            replay.roll(player, dice, state)?
+                        }
+                    };
+
+                    self.moves.push(SgfMove {
+                        action,
+                        comment: None,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(comment) = comment {
+            match self.moves.last_mut() {
+                Some(m) if replay.is_some() => m.comment = Some(comment),
+                _ => self.comment = Some(comment),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for SgfGame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [light, dark] = &self.names;
+        let [light_score, dark_score] = self.score;
+
+        write!(f, "(;FF[4]GM[6]CA[UTF-8]AP[backgammon-sim]")?;
+        write!(
+            f,
+            "MI[length:{}][game:{}][ws:{light_score}][bs:{dark_score}]",
+            self.match_length, self.game_number
+        )?;
+        write!(f, "PW[{}]PB[{}]", escape(light), escape(dark))?;
+
+        if self.crawford {
+            write!(f, "RU[Crawford:CrawfordGame]")?;
+        }
+
+        if let Some((winner, points)) = self.winner {
+            let colour = if winner { 'W' } else { 'B' };
+            write!(f, "RE[{colour}+{points}]")?;
+        }
+
+        if self.start != GameState::new_with_default_setup() {
+            write!(f, "AE[a:{BAR}]")?;
+
+            for (property, sign) in [("AW", 1), ("AB", -1)] {
+                let mut points = String::new();
+                for i in 0..24 {
+                    let n = self.start.checkers(i) * sign;
+                    for _ in 0..n.max(0) {
+                        points.push(point_letter(i as u8));
+                    }
+                }
+                // Light is index 0.
+                let bar = self.start.bar()[(sign < 0) as usize];
+                points.extend((0..bar).map(|_| BAR));
+
+                if !points.is_empty() {
+                    write!(f, "{property}")?;
+                    for point in points.chars() {
+                        write!(f, "[{point}]")?;
+                    }
+                }
+            }
+        }
+
+        if let Some(comment) = &self.comment {
+            write!(f, "C[{}]", escape(comment))?;
+        }
+
+        for m in &self.moves {
+            let colour = if m.action.player() { 'W' } else { 'B' };
+            write!(f, "\n;{colour}[")?;
+
+            match &m.action {
+                Action::Roll(turn) => {
+                    let [d1, d2] = turn.dice;
+                    write!(f, "{d1}{d2}")?;
+
+                    for &[from, die] in &turn.play {
+                        let to = landing(turn.turn, from, die);
+                        write!(
+                            f,
+                            "{}{}",
+                            point_letter(from),
+                            to.map_or(OFF, point_letter)
+                        )?;
+                    }
+                }
+                Action::Double { .. } => write!(f, "double")?,
+                Action::Take(_) => write!(f, "take")?,
+                Action::Drop(_) => write!(f, "drop")?,
+                Action::Beaver(_) => write!(f, "beaver")?,
+            }
+            write!(f, "]")?;
+
+            if let Some(comment) = &m.comment {
+                write!(f, "C[{}]", escape(comment))?;
+            }
+        }
+
+        writeln!(f, ")")
+    }
+}
+
+/// All the games of an SGF file, each its own game tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SgfFile {
+    pub games: Vec<SgfGame>,
+}
+
+impl Display for SgfFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.games.iter().try_for_each(|game| write!(f, "{game}"))
+    }
+}
+
+/// Reads every game tree, replaying the moves to check they are legal.
+impl FromStr for SgfFile {
+    type Err = SgfError;
+
+    fn from_str(text: &str) -> Result<Self, SgfError> {
+        let mut parser = Parser {
+            text,
+            chars: text.char_indices().peekable(),
+        };
+        let mut games = Vec::new();
+
+        loop {
+            parser.skip_whitespace();
+            if parser.chars.peek().is_none() {
+                break;
+            }
+
+            let game = games.len();
+            let mut nodes = Vec::new();
+            parser.tree(&mut nodes).map_err(|offset| SgfError {
+                game,
+                node: nodes.len(),
+                kind: SgfErrorKind::Syntax(offset),
+            })?;
+
+            games.push(
+                SgfGame::from_nodes(&nodes)
+                    .map_err(|(node, kind)| SgfError { game, node, kind })?,
+            );
+        }
+
+        Ok(Self { games })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SgfError {
+    /// The game tree, counting from 0.
+    pub game: usize,
+    /// The node on the game's main line, with the root as 0.
+    pub node: usize,
+    pub kind: SgfErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SgfErrorKind {
+    /// Not well formed at the byte offset.
+    Syntax(usize),
+    /// A game other than backgammon.
+    NotBackgammon,
+    Value {
+        property: String,
+        value: String,
+    },
+    /// A step of a move that cannot be made.
+    Move(MoveError),
+    /// A move reaching a position the roll does not allow.
+    Illegal,
+    /// A player rolling twice in a row, or doubling when not on roll.
+    OutOfTurn,
+    /// A cube action out of sequence, or doubling in the Crawford game.
+    Cube,
+    /// An action after the game has ended.
+    GameOver,
+    /// The game is credited to the player who lost it.
+    Winner,
+    /// Setting up the position after the first move.
+    Setup,
+}
+
+impl From<ReplayError> for SgfErrorKind {
+    fn from(e: ReplayError) -> Self {
+        match e {
+            ReplayError::OutOfTurn => Self::OutOfTurn,
+            ReplayError::Cube => Self::Cube,
+            ReplayError::GameOver => Self::GameOver,
+            ReplayError::Illegal => Self::Illegal,
+            ReplayError::Winner => Self::Winner,
+        }
+    }
+}
+
+impl Display for SgfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}, node {}: ", self.game, self.node)?;
+
+        match &self.kind {
+            SgfErrorKind::Syntax(offset) => {
+                write!(f, "Malformed SGF at byte {offset}")
+            }
+            SgfErrorKind::NotBackgammon => write!(f, "Not a backgammon game"),
+            SgfErrorKind::Value { property, value } => {
+                write!(f, "Invalid value {value:?} for {property}")
+            }
+            SgfErrorKind::Move(e) => write!(f, "{e}"),
+            SgfErrorKind::Illegal => write!(f, "Illegal play for the roll"),
+            SgfErrorKind::OutOfTurn => write!(f, "Played out of turn"),
+            SgfErrorKind::Cube => write!(f, "Unexpected cube action"),
+            SgfErrorKind::GameOver => write!(f, "The game is already over"),
+            SgfErrorKind::Winner => write!(f, "Wrong winner"),
+            SgfErrorKind::Setup => {
+                write!(f, "The position is set up after the first move")
+            }
+        }
+    }
+}
+
+impl Error for SgfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            SgfErrorKind::Move(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// A property name with its values.
+type Node = Vec<(String, Vec<String>)>;
+
+struct Parser<'a> {
+    text: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+// The methods return the byte offset of the first unexpected character on
+// failure.
+impl Parser<'_> {
+    // Appends the main line of the tree to `nodes`.
+    fn tree(&mut self, nodes: &mut Vec<Node>) -> Result<(), usize> {
+        self.expect('(')?;
+
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some((_, ';')) => {
+                    self.chars.next();
+                    nodes.push(self.node()?);
+                }
+                _ => break,
+            }
+        }
+
+        // Only the first variation continues the main line.
+        let mut main_line = true;
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some((_, '(')) if main_line => {
+                    self.tree(nodes)?;
+                    main_line = false;
+                }
+                Some((_, '(')) => self.tree(&mut Vec::new())?,
+                _ => break,
+            }
+        }
+
+        self.skip_whitespace();
+        self.expect(')')
+    }
+
+    fn node(&mut self) -> Result<Node, usize> {
+        let mut node = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            let mut name = String::new();
+            while let Some((_, c)) =
+                self.chars.next_if(|(_, c)| c.is_ascii_alphabetic())
+            {
+                name.push(c);
+            }
+            if name.is_empty() {
+                return Ok(node);
+            }
+
+            let mut values = Vec::new();
+            loop {
+                self.skip_whitespace();
+                if self.chars.next_if(|&(_, c)| c == '[').is_none() {
+                    break;
+                }
+                values.push(self.value()?);
+            }
+
+            if values.is_empty() {
+                return Err(self.offset());
+            }
+            node.push((name, values));
+        }
+    }
+
+    // The text up to the closing bracket, unescaped.
+    fn value(&mut self) -> Result<String, usize> {
+        let mut value = String::new();
+
+        loop {
+            match self.chars.next().ok_or(self.text.len())? {
+                (_, ']') => return Ok(value),
+                (_, '\\') => match self.chars.next().ok_or(self.text.len())? {
+                    // An escaped line break is removed.
+                    (_, '\n') => {}
+                    (_, c) => value.push(c),
+                },
+                (_, c) => value.push(c),
+            }
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), usize> {
+        match self.chars.next_if(|&(_, next)| next == c) {
+            Some(_) => Ok(()),
+            None => Err(self.offset()),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn offset(&mut self) -> usize {
+        self.chars.peek().map_or(self.text.len(), |&(i, _)| i)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+fn point_letter(index: u8) -> char {
+    match index {
+        SPECIAL_MOVE => BAR,
+        i => (b'a' + i) as char,
+    }
+}
+
+// The index of a letter, with `None` for off.
+fn letter_point(letter: char) -> Option<u8> {
+    match letter {
+        BAR => Some(SPECIAL_MOVE),
+        'a'..='x' => Some(letter as u8 - b'a'),
+        _ => None,
+    }
+}
+
+// Where a checker moved by `turn` from `from` with `die` ends up, with `None`
+// for off.
+fn landing(turn: bool, from: u8, die: u8) -> Option<u8> {
+    let to = match (turn, from) {
+        (true, SPECIAL_MOVE) => die as i8 - 1,
+        (false, SPECIAL_MOVE) => 24 - die as i8,
+        (true, from) => (from + die) as i8,
+        (false, from) => from as i8 - die as i8,
+    };
+
+    (0..24).contains(&to).then_some(to as u8)
+}
+
+// From and to, with `None` as `to` for bearing off.
+type Step = (u8, Option<u8>);
+
+// `31qtst` into the dice and the steps.
+fn parse_roll(value: &str) -> Option<([u8; 2], Vec<Step>)> {
+    let mut chars = value.chars();
+    let mut die = || match chars.next()? {
+        d @ '1'..='6' => Some(d as u8 - b'0'),
+        _ => None,
+    };
+    let dice = [die()?, die()?];
+
+    let letters: Vec<_> = chars.collect();
+    if letters.len() % 2 != 0 || letters.len() > 8 {
+        return None;
+    }
+
+    let steps = letters
+        .chunks(2)
+        .map(|pair| match *pair {
+            [from, to] if from != OFF && to != BAR => {
+                let valid = |c: char| ('a'..=OFF).contains(&c);
+                (valid(from) && valid(to))
+                    .then(|| (letter_point(from).unwrap(), letter_point(to)))
+            }
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    Some((dice, steps))
+}
+
+// Makes each step with the die matching its distance.
+fn play_steps(
+    mut state: GameState,
+    turn: bool,
+    steps: &[Step],
+) -> Result<GameState, SgfErrorKind> {
+    for &(from, to) in steps {
+        // Positions along the path of `turn`, with the bar before the first
+        // point and off after the last.
+        let position = |index: Option<u8>| match (index, turn) {
+            (Some(SPECIAL_MOVE), _) => 0,
+            (None, _) => 25,
+            (Some(i), true) => i as i32 + 1,
+            (Some(i), false) => 24 - i as i32,
+        };
+
+        let distance = position(to) - position(Some(from));
+        if !(1..=6).contains(&distance) {
+            return Err(SgfErrorKind::Illegal);
+        }
+
+        state = state
+            .do_move(turn, from, distance as u8)
+            .map_err(SgfErrorKind::Move)?;
+    }
+
+    Ok(state)
+}
+
+// Applies `AE`, `AW` or `AB`. On failure, the value at fault, if any.
+fn set_up<'a>(
+    state: GameState,
+    property: &str,
+    values: &'a [String],
+) -> Result<GameState, Option<&'a str>> {
+    let mut checkers: [i8; 24] = std::array::from_fn(|i| state.checkers(i));
+    let mut bar = state.bar();
+
+    for value in values {
+        let mut letters = value.chars();
+        let range = match (letters.next(), letters.next(), letters.next()) {
+            (Some(a), None, None) => a..=a,
+            (Some(a), Some(':'), Some(b)) if letters.next().is_none() => a..=b,
+            _ => return Err(Some(value)),
+        };
+
+        for letter in range {
+            let point = letter_point(letter).ok_or(Some(value.as_str()))?;
+
+            match (property, point) {
+                ("AE", SPECIAL_MOVE) => bar = [0, 0],
+                ("AE", i) => checkers[i as usize] = 0,
+                ("AW", SPECIAL_MOVE) => bar[0] += 1,
+                ("AB", SPECIAL_MOVE) => bar[1] += 1,
+                (_, i) => {
+                    let sign = if property == "AW" { 1 } else { -1 };
+                    if checkers[i as usize] * sign < 0 {
+                        return Err(Some(value));
+                    }
+                    checkers[i as usize] += sign;
+                }
+            }
+        }
+    }
+
+    let mut on_board = bar.map(u32::from);
+    for n in checkers {
+        on_board[(n < 0) as usize] += n.unsigned_abs() as u32;
+    }
+    if on_board.iter().any(|&n| n > 15) {
+        return Err(None);
+    }
+
+    Ok(GameState::from_parts(
+        checkers,
+        bar,
+        on_board.map(|n| 15 - n as u8),
+    ))
+}
+
+// `W+2` or `B+1R`, or `None` inside for a game without a winner.
+fn parse_result(value: &str) -> Option<Option<(bool, u32)>> {
+    if value == "0" || value == "?" || value == "Void" {
+        return Some(None);
+    }
+
+    let (colour, rest) = value.split_once('+')?;
+    let winner = match colour {
+        "W" => true,
+        "B" => false,
+        _ => return None,
+    };
+    let points: String =
+        rest.chars().take_while(char::is_ascii_digit).collect();
+
+    Some(Some((winner, points.parse().ok()?)))
+}
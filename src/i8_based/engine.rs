@@ -0,0 +1,63 @@
+use crate::backgammon::Backgammon;
+
+use super::{GameState, MoveBuffer, SPECIAL_MOVE};
+
+impl Backgammon for GameState {
+    // [from, die], with `from` being SPECIAL_MOVE when entering from the bar.
+    type Move = [u8; 2];
+
+    fn empty() -> Self {
+        Self::new()
+    }
+
+    fn default_setup() -> Self {
+        Self::new_with_default_setup()
+    }
+
+    fn from_parts(
+        checkers: [i8; 24],
+        bar: [u8; 2],
+        borne_off: [u8; 2],
+    ) -> Self {
+        Self {
+            tiles: checkers,
+            captured: bar,
+            finished: borne_off,
+        }
+    }
+
+    fn checkers(&self, point: usize) -> i8 {
+        self.tiles[point]
+    }
+
+    fn bar(&self) -> [u8; 2] {
+        self.captured
+    }
+
+    fn borne_off(&self) -> [u8; 2] {
+        self.finished
+    }
+
+    fn pip_counts(&self) -> [u32; 2] {
+        self.get_tot_dist()
+    }
+
+    fn legal_moves(&self, turn: bool, die: u8) -> Vec<[u8; 2]> {
+        (0..24)
+            .chain([SPECIAL_MOVE])
+            .filter(|&from| self.do_move(turn, from, die).is_ok())
+            .map(|from| [from, die])
+            .collect()
+    }
+
+    fn apply_move(&self, turn: bool, [from, die]: [u8; 2]) -> Option<Self> {
+        self.do_move(turn, from, die).ok()
+    }
+
+    fn legal_plays(&self, turn: bool, dice: [u8; 2]) -> Vec<Self> {
+        let mut moves = MoveBuffer::new();
+        moves.generate(turn, *self, dice);
+
+        moves.state_iterator().collect()
+    }
+}
@@ -0,0 +1,107 @@
+use std::{error::Error, fmt::Display};
+
+/// Why [`GameState::do_move`](super::GameState::do_move) rejected a move.
+///
+/// Every variant carries the player (`turn`), the point moved from (`from`,
+/// [`SPECIAL_MOVE`](super::SPECIAL_MOVE) for the bar) and the die used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveError {
+    /// `from` is neither a point nor the bar.
+    IllegalSpace { turn: bool, from: u8, die: u8 },
+    /// Tried to enter from the bar without any checkers on it.
+    NoCapturedPieces { turn: bool, from: u8, die: u8 },
+    /// `from` holds none of the moving player's checkers.
+    NoMovablePieces { turn: bool, from: u8, die: u8 },
+    /// Tried to move a checker on the board while some are still on the bar.
+    PiecesCaptured { turn: bool, from: u8, die: u8 },
+    /// `to` is held by two or more opposing checkers.
+    TargetOccupied {
+        turn: bool,
+        from: u8,
+        to: u8,
+        die: u8,
+    },
+    /// Tried to bear off before every checker is in the home board.
+    NotAllHome { turn: bool, from: u8, die: u8 },
+    /// Tried to bear off with a larger die than needed while checkers remain
+    /// further from home.
+    FullMovesAvailable { turn: bool, from: u8, die: u8 },
+}
+
+impl MoveError {
+    pub fn turn(&self) -> bool {
+        match *self {
+            Self::IllegalSpace { turn, .. }
+            | Self::NoCapturedPieces { turn, .. }
+            | Self::NoMovablePieces { turn, .. }
+            | Self::PiecesCaptured { turn, .. }
+            | Self::TargetOccupied { turn, .. }
+            | Self::NotAllHome { turn, .. }
+            | Self::FullMovesAvailable { turn, .. } => turn,
+        }
+    }
+
+    pub fn from(&self) -> u8 {
+        match *self {
+            Self::IllegalSpace { from, .. }
+            | Self::NoCapturedPieces { from, .. }
+            | Self::NoMovablePieces { from, .. }
+            | Self::PiecesCaptured { from, .. }
+            | Self::TargetOccupied { from, .. }
+            | Self::NotAllHome { from, .. }
+            | Self::FullMovesAvailable { from, .. } => from,
+        }
+    }
+
+    pub fn die(&self) -> u8 {
+        match *self {
+            Self::IllegalSpace { die, .. }
+            | Self::NoCapturedPieces { die, .. }
+            | Self::NoMovablePieces { die, .. }
+            | Self::PiecesCaptured { die, .. }
+            | Self::TargetOccupied { die, .. }
+            | Self::NotAllHome { die, .. }
+            | Self::FullMovesAvailable { die, .. } => die,
+        }
+    }
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let player = if self.turn() { "Light" } else { "Dark" };
+        let die = self.die();
+
+        match *self {
+            Self::IllegalSpace { from, .. } => {
+                write!(f, "Illegal space {from}")
+            }
+            Self::NoCapturedPieces { .. } => {
+                write!(f, "{player} has no captured pieces to enter with {die}")
+            }
+            Self::NoMovablePieces { from, .. } => {
+                write!(f, "{player} has no pieces to move on {from}")
+            }
+            Self::PiecesCaptured { from, .. } => write!(
+                f,
+                "{player} must enter captured pieces before moving from {from}"
+            ),
+            Self::TargetOccupied { from, to, .. } => write!(
+                f,
+                "Target spot {to} occupied, {player} cannot move {die} from \
+                 {from}"
+            ),
+            Self::NotAllHome { from, .. } => write!(
+                f,
+                "{player} cannot bear off from {from} before all pieces are \
+                 home"
+            ),
+            Self::FullMovesAvailable { from, .. } => write!(
+                f,
+                "{player} cannot bear off from {from} with {die} while full \
+                 moves are available"
+            ),
+        }
+    }
+}
+
+impl Error for MoveError {}
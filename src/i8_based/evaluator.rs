@@ -1,32 +1,59 @@
-use super::{GameState, MoveBuffer};
+use crate::{
+    backgammon::Backgammon,
+    cube::{Chances, Cube, CubefulEquity},
+    probabilities::Probabilities,
+};
 
-impl GameState {
-    fn get_net_dist(&self) -> i32 {
-        let [light_dist, dark_dist] = self.get_tot_dist();
+use super::{GameState, MoveBuffer, PipCount, Play, StaticEval};
 
-        (light_dist as i32) - (dark_dist as i32)
-    }
+/// Expectiminimax search over all rolls, scoring leaves with a
+/// [`StaticEval`], by default [`PipCount`].
+///
+/// Evaluations are the [`Probabilities`] of one player, and each player picks
+/// the play with the highest cubeless equity for them. Finished games are
+/// exact and are not searched past.
+pub struct Evaluator<E = PipCount> {
+    static_eval: E,
+    move_buffers: Vec<MoveBuffer>,
 }
 
-pub struct Evaluator {
-    move_buffers: Vec<MoveBuffer>,
+impl Default for Evaluator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Evaluator {
     pub fn new() -> Self {
-        Self { move_buffers: Vec::new() }
+        Self::with_eval(PipCount)
+    }
+}
+
+impl<E: StaticEval> Evaluator<E> {
+    pub fn with_eval(static_eval: E) -> Self {
+        Self {
+            static_eval,
+            move_buffers: Vec::new(),
+        }
     }
 
+    pub fn static_eval(&self) -> &E {
+        &self.static_eval
+    }
+
+    /// Expected chances of `turn`, about to roll, searching `depth` plies.
     pub fn get_brute_force_eval(
         &mut self,
         state: GameState,
         turn: bool,
         depth: u32,
-    ) -> f64 {
-        if depth == 0 {
-            state.get_net_dist() as f64
+    ) -> Probabilities {
+        if let Some(result) = state.result() {
+            Probabilities::from_result(result, turn)
+        } else if depth == 0 {
+            self.static_eval.eval(&state, turn)
         } else {
-            let mut eval = 0.0;
+            let mut eval = Probabilities::default();
 
             let mut moves = self.move_buffers.pop().unwrap_or_default();
 
@@ -35,10 +62,14 @@ impl Evaluator {
                 for d2 in d1..=6 {
                     let p = if d1 == d2 { p } else { 2.0 * p };
 
-                    if let Some((_, e)) =
-                        self.get_best_move(state, turn, [d1, d2], depth, &mut moves)
-                    {
-                        eval += p * e;
+                    if let Some((_, _, e)) = self.get_best_move(
+                        state,
+                        turn,
+                        [d1, d2],
+                        depth,
+                        &mut moves,
+                    ) {
+                        eval = eval + e * p;
                     } else {
                         panic!("No legal moves!");
                     }
@@ -51,6 +82,9 @@ impl Evaluator {
         }
     }
 
+    /// The best play for `turn` with `dice`, the position it leads to and the
+    /// chances of `turn` after it. `depth` counts this ply, so it must be at
+    /// least 1.
     pub fn get_best_move(
         &mut self,
         state: GameState,
@@ -58,24 +92,44 @@ impl Evaluator {
         dice: [u8; 2],
         depth: u32,
         moves: &mut MoveBuffer,
-    ) -> Option<(GameState, f64)> {
+    ) -> Option<(Play, GameState, Probabilities)> {
         moves.generate(turn, state, dice);
 
-        let mut best = None;
+        let mut best: Option<(Play, GameState, Probabilities)> = None;
 
-        for new_state in moves.state_iterator() {
-            let eval = self.get_brute_force_eval(new_state, !turn, depth - 1);
+        for (play, new_state) in moves.play_iterator() {
+            let eval = self
+                .get_brute_force_eval(new_state, !turn, depth - 1)
+                .flip();
 
-            if let Some((s, e)) = &mut best {
-                if (*e < eval) ^ turn {
+            if let Some((p, s, e)) = &mut best {
+                if eval.cubeless_equity() >= e.cubeless_equity() {
+                    *p = play;
                     *s = new_state;
                     *e = eval;
                 }
             } else {
-                best = Some((new_state, eval))
+                best = Some((play, new_state, eval))
             }
         }
 
         best
     }
+
+    /// Cube decision for `turn`, about to roll, with `cube`, searching `depth`
+    /// plies. The equities are per point of the current cube value and
+    /// assume `turn` may double.
+    pub fn get_cubeful_eval(
+        &mut self,
+        state: GameState,
+        turn: bool,
+        cube: Cube,
+        depth: u32,
+    ) -> CubefulEquity {
+        let probs = self.get_brute_force_eval(state, turn, depth);
+        CubefulEquity::new(
+            &Chances::from(&probs),
+            cube.owner.map(|owner| owner == turn),
+        )
+    }
 }
@@ -1,23 +1,56 @@
-use std::{cmp::Ordering, time::Instant};
+use std::cmp::Ordering;
 
-use evaluator::Evaluator;
-use move_generator::MoveBuffer;
+use arrayvec::ArrayVec;
+
+pub use error::MoveError;
+pub use evaluator::Evaluator;
+pub use move_generator::{MoveBuffer, PlayIterator, StateIterator};
+pub use position_id::PositionIdError;
+pub use static_eval::{
+    Heuristic, NetError, NetErrorKind, NeuralNet, PipCount, RaceFormula,
+    StaticEval,
+};
 
 mod display;
+mod engine;
+mod error;
 mod evaluator;
 mod move_generator;
+pub mod notation;
+mod position_id;
+#[cfg(feature = "serde")]
+mod serialization;
+mod static_eval;
+
+/// Stands in for `from` in [`GameState::do_move`] when entering a checker
+/// from the bar.
+pub const SPECIAL_MOVE: u8 = 99;
 
-const SPECIAL_MOVE: u8 = 99;
+/// The checker movements of a turn as `[from, die]` steps, in the order
+/// they are applied with [`GameState::do_move`].
+pub type Play = ArrayVec<[u8; 2], 4>;
 
 // Light: (Positive, forward, true)
 // Dark:  (Negative, backward, false)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "serialization::Position"),
+    serde(try_from = "serialization::Position")
+)]
 pub struct GameState {
     tiles: [i8; 24],
     captured: [u8; 2],
     finished: [u8; 2],
 }
 
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GameState {
     pub fn new() -> Self {
         Self {
@@ -83,7 +116,7 @@ impl GameState {
         turn: bool,
         from: u8,
         n: u8,
-    ) -> Result<Self, &'static str> {
+    ) -> Result<Self, MoveError> {
         match from {
             SPECIAL_MOVE => {
                 if self.captured[(!turn) as usize] > 0 {
@@ -114,18 +147,25 @@ impl GameState {
                             Ok(self)
                         }
                         (true, -128..=-2) | (false, 2..=127) => {
-                            Err("Target spot occupied") // Checked
+                            Err(MoveError::TargetOccupied {
+                                turn,
+                                from,
+                                to: target_spot as u8,
+                                die: n,
+                            }) // Checked
                         }
                     }
                 } else {
-                    Err("No captures pieces") // Checked
+                    Err(MoveError::NoCapturedPieces { turn, from, die: n }) // Checked
                 }
             }
             0..=23 => {
                 if turn && self.tiles[from as usize] <= 0
                     || !turn && self.tiles[from as usize] >= 0
                 {
-                    Err("No movable pieces to move") // Checked
+                    Err(MoveError::NoMovablePieces { turn, from, die: n }) // Checked
+                } else if self.captured[(!turn) as usize] > 0 {
+                    Err(MoveError::PiecesCaptured { turn, from, die: n }) // Checked
                 } else {
                     let target_spot = from as isize
                         + if turn { n as isize } else { -(n as isize) };
@@ -158,7 +198,12 @@ impl GameState {
                                     Ok(self)
                                 }
                                 (true, -128..=-2) | (false, 2..=127) => {
-                                    Err("Target spot occupied") // Checked
+                                    Err(MoveError::TargetOccupied {
+                                        turn,
+                                        from,
+                                        to: target_spot as u8,
+                                        die: n,
+                                    }) // Checked
                                 }
                             }
                         }
@@ -179,7 +224,11 @@ impl GameState {
                                         if (18..from)
                                             .any(|i| self.tiles[i as usize] > 0)
                                         {
-                                            Err("Full moves available") // Checked
+                                            Err(MoveError::FullMovesAvailable {
+                                                turn,
+                                                from,
+                                                die: n,
+                                            }) // Checked
                                         } else {
                                             self.tiles[from as usize] -= 1;
                                             self.finished[0] += 1;
@@ -190,190 +239,30 @@ impl GameState {
                                         if (from + 1..6)
                                             .any(|i| self.tiles[i as usize] < 0)
                                         {
-                                            Err("Full moves available") // Checked
+                                            Err(MoveError::FullMovesAvailable {
+                                                turn,
+                                                from,
+                                                die: n,
+                                            }) // Checked
                                         } else {
-                                            self.tiles[from as usize] -= 1;
-                                            self.finished[0] += 1;
+                                            self.tiles[from as usize] += 1;
+                                            self.finished[1] += 1;
                                             Ok(self)
                                         }
                                     }
                                 }
                             } else {
-                                Err("All pieces are not home") // Checked
+                                Err(MoveError::NotAllHome {
+                                    turn,
+                                    from,
+                                    die: n,
+                                }) // Checked
                             }
                         }
                     }
                 }
             }
-            _ => Err("Illegal space"), // Checked
+            _ => Err(MoveError::IllegalSpace { turn, from, die: n }), // Checked
         }
     }
 }
-
-pub fn _test1() -> Result<(), &'static str> {
-    let state = GameState::new_with_default_setup();
-
-    let r = state.do_move(true, 100, 6);
-    println!("{r:?}");
-
-    let r = state.do_move(true, 0, 5);
-    println!("{r:?}");
-
-    let state = state.do_move(true, 0, 6)?.do_move(true, 0, 6)?;
-
-    println!("{state}");
-
-    let r = state.do_move(true, 0, 6);
-    println!("{r:?}");
-
-    let r = state.do_move(true, 18, 6);
-    println!("{r:?}");
-
-    let r = state.do_move(true, SPECIAL_MOVE, 3);
-    println!("{r:?}");
-
-    let state = state.do_move(true, 6, 2)?.do_move(false, 7, 1)?;
-    println!("{state}");
-
-    let r = state.do_move(true, SPECIAL_MOVE, 6);
-    println!("{r:?}");
-
-    let state = state.do_move(true, SPECIAL_MOVE, 4)?;
-    println!("{state}");
-
-    let state = state
-        .do_move(true, 3, 18)?
-        .do_move(true, 8, 12)?
-        .do_move(true, 11, 10)?
-        .do_move(true, 11, 10)?
-        .do_move(true, 11, 10)?
-        .do_move(true, 11, 10)?
-        .do_move(true, 11, 10)?
-        .do_move(true, 16, 3)?
-        .do_move(true, 16, 3)?
-        .do_move(true, 16, 3)?;
-    println!("{state}");
-
-    let state = state.do_move(true, 19, 5)?;
-    println!("{state}");
-
-    let r = state.do_move(true, 19, 6);
-    println!("{r:?}");
-
-    let state = state
-        .do_move(false, 23, 20)?
-        .do_move(false, 23, 20)?
-        .do_move(false, 12, 12)?
-        .do_move(false, 12, 12)?
-        .do_move(false, 12, 12)?
-        .do_move(false, 12, 12)?
-        .do_move(false, 12, 12)?
-        .do_move(false, 7, 3)?
-        .do_move(false, 7, 3)?
-        .do_move(false, 6, 3)?
-        .do_move(false, 4, 5)?;
-    println!("{state}");
-
-    let r = state.do_move(false, 4, 6);
-    println!("{r:?}");
-
-    Ok(())
-}
-
-pub fn _test2() -> Result<(), &'static str> {
-    let state = GameState::new_with_default_setup();
-
-    println!("{state}");
-
-    let mut moves = MoveBuffer::new();
-
-    moves.generate(true, state, [1, 1]);
-
-    println!("{moves:?}");
-
-    println!("Moves with only dice: {}", moves.dice[0]);
-
-    for &s in &moves.single[0] {
-        let new_state = state.do_move(true, s, moves.dice[0]).unwrap();
-        println!("{new_state}");
-    }
-
-    println!("Moves with only dice: {}", moves.dice[1]);
-
-    for &s in &moves.single[1] {
-        let new_state = state.do_move(true, s, moves.dice[1]).unwrap();
-        println!("{new_state}");
-    }
-
-    println!("Moves with dice: {:?}", moves.dice);
-
-    for &[s1, s2] in &moves.double[0] {
-        let new_state = state.do_move(true, s1, moves.dice[0])?.do_move(
-            true,
-            s2,
-            moves.dice[1],
-        )?;
-        println!("{new_state}");
-    }
-
-    println!("Reverse moves with dice: {:?}", moves.dice);
-
-    for &[s1, s2] in &moves.double[1] {
-        let new_state = state.do_move(true, s1, moves.dice[1])?.do_move(
-            true,
-            s2,
-            moves.dice[0],
-        )?;
-        println!("{new_state}");
-    }
-
-    println!("Triple moves with die: {:?}", moves.dice[0]);
-
-    for &[s1, s2, s3] in &moves.triple {
-        let new_state = state
-            .do_move(true, s1, moves.dice[0])?
-            .do_move(true, s2, moves.dice[0])?
-            .do_move(true, s3, moves.dice[0])?;
-        println!("{new_state}");
-    }
-
-    println!("Quadruple moves with die: {:?}", moves.dice[0]);
-
-    for &[s1, s2, s3, s4] in &moves.quadruple {
-        let new_state = state
-            .do_move(true, s1, moves.dice[0])?
-            .do_move(true, s2, moves.dice[0])?
-            .do_move(true, s3, moves.dice[0])?
-            .do_move(true, s4, moves.dice[0])?;
-        println!("{new_state}");
-    }
-
-    Ok(())
-}
-
-pub fn _test3() {
-    let state = GameState::new_with_default_setup();
-
-    println!("{state}");
-
-    let mut moves = MoveBuffer::new();
-
-    moves.generate(true, state, [5, 5]);
-
-    for new_state in moves.state_iterator() {
-        println!("{new_state}");
-    }
-}
-
-pub fn _test4() {
-    let state = GameState::new_with_default_setup();
-
-    let mut ev = Evaluator::new();
-
-    let t = Instant::now();
-    let eval = ev.get_brute_force_eval(state, true, 3);
-    let t = t.elapsed();
-
-    println!("{eval}");
-    println!("took {t:?}");
-}
@@ -1,14 +1,22 @@
-use super::{GameState, SPECIAL_MOVE};
+use arrayvec::ArrayVec;
 
+use super::{GameState, Play, SPECIAL_MOVE};
+
+/// Reusable storage for the legal plays of one roll.
+///
+/// Fill it with [`MoveBuffer::generate`] and walk the resulting positions
+/// with [`MoveBuffer::state_iterator`]. Keeping the buffer around between
+/// calls avoids reallocating on every roll.
 #[derive(Debug)]
 pub struct MoveBuffer {
-    pub dice: [u8; 2],
-    pub turn: bool,
-    pub state: GameState,
-    pub single: [Vec<u8>; 2],
-    pub double: [Vec<[u8; 2]>; 2],
-    pub triple: Vec<[u8; 3]>,
-    pub quadruple: Vec<[u8; 4]>,
+    dice: [u8; 2],
+    turn: bool,
+    state: GameState,
+    single: [Vec<u8>; 2],
+    double: [Vec<[u8; 2]>; 2],
+    triple: Vec<[u8; 3]>,
+    quadruple: Vec<[u8; 4]>,
+    duplicates: usize,
 }
 
 impl Default for MoveBuffer {
@@ -27,72 +35,160 @@ impl MoveBuffer {
             double: [const { Vec::new() }; 2],
             triple: Vec::new(),
             quadruple: Vec::new(),
+            duplicates: 0,
+        }
+    }
+
+    // Sources in the order `turn` moves past them: the bar, then the points
+    // from the furthest away from home to the closest.
+    fn move_order(turn: bool) -> [u8; 25] {
+        let mut order = [SPECIAL_MOVE; 25];
+
+        for (i, from) in order[1..].iter_mut().enumerate() {
+            *from = if turn { i as u8 } else { 23 - i as u8 };
+        }
+
+        order
+    }
+
+    // Where `from` comes in the order of `move_order`.
+    fn order_position(turn: bool, from: u8) -> usize {
+        match from {
+            SPECIAL_MOVE => 0,
+            from if turn => from as usize + 1,
+            from => 24 - from as usize,
+        }
+    }
+
+    // Where a checker moved from `from` with `die` ends up, `None` if it is
+    // borne off.
+    fn landing(&self, from: u8, die: u8) -> Option<u8> {
+        let to = match (from, self.turn) {
+            (SPECIAL_MOVE, true) => die as i8 - 1,
+            (SPECIAL_MOVE, false) => 24 - die as i8,
+            (from, true) => (from + die) as i8,
+            (from, false) => from as i8 - die as i8,
+        };
+
+        (0..24).contains(&to).then_some(to as u8)
+    }
+
+    // Whether a play generated before `[from1, die1], [from2, die2]` reaches
+    // the same position. Two moves can only reach the same position as
+    // another pair when they are reordered, swap dice, or are one checker
+    // using both dice, which is the same as another checker taking its place.
+    fn is_transposition(
+        &self,
+        [from1, from2]: [u8; 2],
+        [die1, die2]: [u8; 2],
+        state: GameState,
+    ) -> bool {
+        // Plays with the smaller die first are generated first, each group
+        // in move order.
+        let key = |[from1, from2]: [u8; 2], die1: u8| {
+            (
+                die1 != self.dice[0],
+                Self::order_position(self.turn, from1),
+                Self::order_position(self.turn, from2),
+            )
+        };
+
+        let mut others = ArrayVec::<_, 5>::new();
+        others.push(([from2, from1], [die2, die1]));
+        others.push(([from1, from2], [die2, die1]));
+        others.push(([from2, from1], [die1, die2]));
+
+        // The checker moving with both dice and the die it would use first
+        // the other way around.
+        let chain = if self.landing(from1, die1) == Some(from2) {
+            Some((from1, die2))
+        } else if self.landing(from2, die2) == Some(from1) {
+            Some((from2, die1))
+        } else {
+            None
+        };
+
+        if let Some((from, first)) = chain {
+            let second = die1 + die2 - first;
+            if let Some(mid) = self.landing(from, first) {
+                others.push(([from, mid], [first, second]));
+                others.push(([mid, from], [second, first]));
+            }
         }
+
+        let own = key([from1, from2], die1);
+        others.into_iter().any(|([from1, from2], [die1, die2])| {
+            key([from1, from2], die1) < own
+                && self
+                    .state
+                    .do_move(self.turn, from1, die1)
+                    .and_then(|s| s.do_move(self.turn, from2, die2))
+                    == Ok(state)
+        })
     }
 
+    // Every pair of moves is tried with either die first, keeping a play only
+    // if no play generated before it reaches the same position.
     fn generate_double(&mut self) {
-        for from1 in (0..24).chain([SPECIAL_MOVE]) {
-            if let Ok(state1) =
-                self.state.do_move(self.turn, from1, self.dice[0])
-            {
-                self.single[0].push(from1);
-
-                for from2 in (0..24).chain([SPECIAL_MOVE]) {
-                    if state1.do_move(self.turn, from2, self.dice[1]).is_ok() {
-                        self.double[0].push([from1, from2]);
-                    }
-                }
-            } else if let Ok(state1) =
-                self.state.do_move(self.turn, from1, self.dice[1])
-            {
-                let from2 = if from1 == SPECIAL_MOVE {
-                    if self.turn {
-                        self.dice[1] - 1
-                    } else {
-                        24 - self.dice[1]
-                    }
-                } else if self.turn {
-                    from1 + self.dice[1]
-                } else {
-                    from1 - self.dice[1]
+        let [d0, d1] = self.dice;
+        let order = Self::move_order(self.turn);
+
+        for (i, dice) in [[d0, d1], [d1, d0]].into_iter().enumerate() {
+            for &from1 in &order {
+                let Ok(state1) = self.state.do_move(self.turn, from1, dice[0])
+                else {
+                    continue;
                 };
+                self.single[i].push(from1);
+
+                for &from2 in &order {
+                    let Ok(state2) = state1.do_move(self.turn, from2, dice[1])
+                    else {
+                        continue;
+                    };
 
-                if state1.do_move(self.turn, from2, self.dice[0]).is_ok() {
-                    self.double[1].push([from1, from2]);
+                    if self.is_transposition([from1, from2], dice, state2) {
+                        self.duplicates += 1;
+                    } else {
+                        self.double[i].push([from1, from2]);
+                    }
                 }
             }
+        }
 
-            if self.state.do_move(self.turn, from1, self.dice[1]).is_ok() {
-                self.single[1].push(from1);
-            }
+        // If only one die can be played, it has to be the larger one.
+        if self.double[0].is_empty()
+            && self.double[1].is_empty()
+            && !self.single[1].is_empty()
+        {
+            self.single[0].clear();
         }
     }
 
+    // Each multiset of moves is only generated in the order the checkers are
+    // passed on the way home, so every play leads to a different position.
     fn generate_quadruple(&mut self) {
-        for from1 in (0..24).chain([SPECIAL_MOVE]) {
-            let Ok(state1) = self.state.do_move(self.turn, from1, self.dice[0])
-            else {
+        let die = self.dice[0];
+        let order = Self::move_order(self.turn);
+
+        for (i1, &from1) in order.iter().enumerate() {
+            let Ok(state1) = self.state.do_move(self.turn, from1, die) else {
                 continue;
             };
             self.single[0].push(from1);
-            for from2 in (from1..24).chain([SPECIAL_MOVE]) {
-                let Ok(state2) = state1.do_move(self.turn, from2, self.dice[0])
-                else {
+            for (i2, &from2) in order.iter().enumerate().skip(i1) {
+                let Ok(state2) = state1.do_move(self.turn, from2, die) else {
                     continue;
                 };
                 self.double[0].push([from1, from2]);
-                for from3 in (from2..24).chain([SPECIAL_MOVE]) {
-                    let Ok(state3) =
-                        state2.do_move(self.turn, from3, self.dice[0])
+                for (i3, &from3) in order.iter().enumerate().skip(i2) {
+                    let Ok(state3) = state2.do_move(self.turn, from3, die)
                     else {
                         continue;
                     };
                     self.triple.push([from1, from2, from3]);
-                    for from4 in (from3..24).chain([SPECIAL_MOVE]) {
-                        if state3
-                            .do_move(self.turn, from4, self.dice[0])
-                            .is_ok()
-                        {
+                    for &from4 in &order[i3..] {
+                        if state3.do_move(self.turn, from4, die).is_ok() {
                             self.quadruple.push([from1, from2, from3, from4]);
                         }
                     }
@@ -101,6 +197,9 @@ impl MoveBuffer {
         }
     }
 
+    /// Finds the plays `turn` can make with `dice` from `state`. As many dice
+    /// as possible have to be used, and if only one of two different dice can
+    /// be, it has to be the larger one.
     pub fn generate(
         &mut self,
         turn: bool,
@@ -113,6 +212,7 @@ impl MoveBuffer {
         self.double[1].clear();
         self.triple.clear();
         self.quadruple.clear();
+        self.duplicates = 0;
 
         dice.sort();
 
@@ -127,8 +227,39 @@ impl MoveBuffer {
         }
     }
 
-    pub fn state_iterator(&self) -> StateIterator {
-        use StateIterator::*;
+    /// The dice of the last call to `generate`, sorted in ascending order.
+    pub fn dice(&self) -> [u8; 2] {
+        self.dice
+    }
+
+    /// How many plays the last call to `generate` dropped because another
+    /// play already led to the same position. Reorderings of the moves of a
+    /// double are never generated and are not counted.
+    pub fn duplicates_removed(&self) -> usize {
+        self.duplicates
+    }
+
+    pub fn turn(&self) -> bool {
+        self.turn
+    }
+
+    /// The position the plays were generated from.
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    pub fn state_iterator(&self) -> StateIterator<'_> {
+        StateIterator(self.sequences())
+    }
+
+    /// Like [`MoveBuffer::state_iterator`], but also yields the play leading
+    /// to each position.
+    pub fn play_iterator(&self) -> PlayIterator<'_> {
+        PlayIterator(self.sequences())
+    }
+
+    fn sequences(&self) -> Sequences<'_> {
+        use Sequences::*;
         if !self.quadruple.is_empty() {
             Quadruple(self.state, self.turn, self.dice[0], &self.quadruple, 0)
         } else if !self.triple.is_empty() {
@@ -157,8 +288,52 @@ impl MoveBuffer {
     }
 }
 
+/// Iterator over the distinct positions reachable with the dice given to
+/// [`MoveBuffer::generate`]. Yields the unchanged position once if no checker
+/// can move.
+#[derive(Debug)]
+pub struct StateIterator<'a>(Sequences<'a>);
+
+impl Iterator for StateIterator<'_> {
+    type Item = GameState;
+
+    fn next(&mut self) -> Option<GameState> {
+        self.0.next().map(|(_, state)| state)
+    }
+}
+
+/// Iterator over the distinct positions reachable with the dice given to
+/// [`MoveBuffer::generate`] together with a play reaching each. Yields an
+/// empty play and the unchanged position if no checker can move.
+#[derive(Debug)]
+pub struct PlayIterator<'a>(Sequences<'a>);
+
+impl Iterator for PlayIterator<'_> {
+    type Item = (Play, GameState);
+
+    fn next(&mut self) -> Option<(Play, GameState)> {
+        self.0.next()
+    }
+}
+
+fn apply_steps(
+    state: GameState,
+    turn: bool,
+    steps: impl IntoIterator<Item = [u8; 2]>,
+) -> (Play, GameState) {
+    let mut play = Play::new();
+    let mut state = state;
+
+    for [from, die] in steps {
+        state = state.do_move(turn, from, die).unwrap();
+        play.push([from, die]);
+    }
+
+    (play, state)
+}
+
 #[derive(Debug)]
-pub enum StateIterator<'a> {
+enum Sequences<'a> {
     NoMoves(GameState, bool),
     Single(GameState, bool, [u8; 2], [&'a [u8]; 2], bool, usize),
     Double(GameState, bool, [u8; 2], [&'a [[u8; 2]]; 2], bool, usize),
@@ -166,34 +341,34 @@ pub enum StateIterator<'a> {
     Quadruple(GameState, bool, u8, &'a [[u8; 4]], usize),
 }
 
-impl<'a> Iterator for StateIterator<'a> {
-    type Item = GameState;
+impl Iterator for Sequences<'_> {
+    type Item = (Play, GameState);
 
-    fn next(&mut self) -> Option<GameState> {
-        use StateIterator::*;
+    fn next(&mut self) -> Option<(Play, GameState)> {
+        use Sequences::*;
         match self {
             NoMoves(state, should_return) => should_return.then(|| {
                 *should_return = false;
-                *state
+                (Play::new(), *state)
             }),
             Single(state, turn, dice, moves, first, i) => {
                 if *first {
                     if let Some(&from) = moves[0].get(*i) {
                         *i += 1;
 
-                        Some(state.do_move(*turn, from, dice[0]).unwrap())
+                        Some(apply_steps(*state, *turn, [[from, dice[0]]]))
                     } else if let Some(&from) = moves[1].first() {
                         *i = 1;
                         *first = false;
 
-                        Some(state.do_move(*turn, from, dice[1]).unwrap())
+                        Some(apply_steps(*state, *turn, [[from, dice[1]]]))
                     } else {
                         None
                     }
                 } else if let Some(&from) = moves[1].get(*i) {
                     *i += 1;
 
-                    Some(state.do_move(*turn, from, dice[1]).unwrap())
+                    Some(apply_steps(*state, *turn, [[from, dice[1]]]))
                 } else {
                     None
                 }
@@ -203,63 +378,45 @@ impl<'a> Iterator for StateIterator<'a> {
                     if let Some(&[from1, from2]) = moves[0].get(*i) {
                         *i += 1;
 
-                        Some(
-                            state
-                                .do_move(*turn, from1, dice[0])
-                                .unwrap()
-                                .do_move(*turn, from2, dice[1])
-                                .unwrap(),
-                        )
+                        Some(apply_steps(
+                            *state,
+                            *turn,
+                            [[from1, dice[0]], [from2, dice[1]]],
+                        ))
                     } else if let Some(&[from1, from2]) = moves[1].first() {
                         *i = 1;
                         *forwards = false;
 
-                        Some(
-                            state
-                                .do_move(*turn, from1, dice[1])
-                                .unwrap()
-                                .do_move(*turn, from2, dice[0])
-                                .unwrap(),
-                        )
+                        Some(apply_steps(
+                            *state,
+                            *turn,
+                            [[from1, dice[1]], [from2, dice[0]]],
+                        ))
                     } else {
                         None
                     }
                 } else if let Some(&[from1, from2]) = moves[1].get(*i) {
                     *i += 1;
 
-                    Some(
-                        state
-                            .do_move(*turn, from1, dice[1])
-                            .unwrap()
-                            .do_move(*turn, from2, dice[0])
-                            .unwrap(),
-                    )
+                    Some(apply_steps(
+                        *state,
+                        *turn,
+                        [[from1, dice[1]], [from2, dice[0]]],
+                    ))
                 } else {
                     None
                 }
             }
-            Triple(state, turn, die, moves, i) => moves.get(*i).map(|&froms| {
+            Triple(state, turn, die, moves, i) => moves.get(*i).map(|froms| {
                 *i += 1;
 
-                let mut state = *state;
-
-                for from in froms {
-                    state = state.do_move(*turn, from, *die).unwrap();
-                }
-
-                state
+                apply_steps(*state, *turn, froms.map(|from| [from, *die]))
             }),
             Quadruple(state, turn, die, moves, i) => {
-                moves.get(*i).map(|&froms| {
+                moves.get(*i).map(|froms| {
                     *i += 1;
 
-                    let mut state = *state;
-
-                    for from in froms {
-                        state = state.do_move(*turn, from, *die).unwrap();
-                    }
-
-                    state
+                    apply_steps(*state, *turn, froms.map(|from| [from, *die]))
                 })
             }
         }
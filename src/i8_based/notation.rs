@@ -0,0 +1,431 @@
+//! Standard backgammon move notation, e.g. `24/18 13/11*`, `bar/22`,
+//! `6/off` and `8/5(2)`.
+//!
+//! Points are numbered from the moving player's side: 1 to 24 counting from
+//! their home board outwards, with the bar as 25 and bearing off as 0.
+
+use std::{error::Error, fmt::Display};
+
+use super::{GameState, MoveBuffer, MoveError, Play, SPECIAL_MOVE};
+
+pub const BAR_POINT: u8 = 25;
+pub const OFF_POINT: u8 = 0;
+
+/// The number `turn` calls the point at `index`, with
+/// [`SPECIAL_MOVE`] mapping to [`BAR_POINT`].
+pub fn point_number(turn: bool, index: u8) -> u8 {
+    match index {
+        SPECIAL_MOVE => BAR_POINT,
+        _ if turn => 24 - index,
+        _ => index + 1,
+    }
+}
+
+/// Inverse of [`point_number`]. `None` for [`OFF_POINT`] and out of range
+/// points.
+pub fn point_index(turn: bool, point: u8) -> Option<u8> {
+    match point {
+        BAR_POINT => Some(SPECIAL_MOVE),
+        1..=24 if turn => Some(24 - point),
+        1..=24 => Some(point - 1),
+        _ => None,
+    }
+}
+
+/// A checker moving from one point to another, possibly using several dice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotatedMove {
+    pub from: u8,
+    pub to: u8,
+    pub hit: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    /// A part of the text that is not a move.
+    Syntax(String),
+    TooManyMoves,
+    /// The moves cannot be made with the dice. Holds the reason given by
+    /// [`GameState::do_move`] for the first step that failed, if any.
+    Unplayable(Option<MoveError>),
+    /// The moves can be made, but a play using more dice, or the larger die,
+    /// is available.
+    Illegal,
+}
+
+impl Display for NotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(token) => write!(f, "Could not parse move {token:?}"),
+            Self::TooManyMoves => write!(f, "Too many moves"),
+            Self::Unplayable(Some(e)) => {
+                write!(f, "Unplayable: {}", numbered(*e))
+            }
+            Self::Unplayable(None) => {
+                write!(f, "The moves do not match the dice")
+            }
+            Self::Illegal => write!(f, "More of the dice must be used"),
+        }
+    }
+}
+
+impl Error for NotationError {}
+
+// `e` with its points numbered from the moving player's side, so that it
+// reads like the text it came from.
+fn numbered(e: MoveError) -> MoveError {
+    use MoveError::*;
+
+    let turn = e.turn();
+    let point = |index| point_number(turn, index);
+
+    match e {
+        // Not a point, so left as it is.
+        IllegalSpace { .. } | NoCapturedPieces { .. } => e,
+        NoMovablePieces { turn, from, die } => NoMovablePieces {
+            turn,
+            from: point(from),
+            die,
+        },
+        PiecesCaptured { turn, from, die } => PiecesCaptured {
+            turn,
+            from: point(from),
+            die,
+        },
+        TargetOccupied {
+            turn,
+            from,
+            to,
+            die,
+        } => TargetOccupied {
+            turn,
+            from: point(from),
+            to: point(to),
+            die,
+        },
+        NotAllHome { turn, from, die } => NotAllHome {
+            turn,
+            from: point(from),
+            die,
+        },
+        FullMovesAvailable { turn, from, die } => FullMovesAvailable {
+            turn,
+            from: point(from),
+            die,
+        },
+    }
+}
+
+/// Parses the text of a play into single checker moves. Chains like
+/// `13/7*/5` become one move per segment and `8/5(2)` is repeated.
+pub fn parse_moves(text: &str) -> Result<Vec<NotatedMove>, NotationError> {
+    let mut moves = Vec::new();
+
+    for token in text.split_whitespace() {
+        let syntax_err = || NotationError::Syntax(token.to_owned());
+
+        let (path, count) = match token.split_once('(') {
+            Some((path, count)) => (
+                path,
+                count
+                    .strip_suffix(')')
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(syntax_err)?,
+            ),
+            None => (token, 1),
+        };
+
+        let mut points = path.split('/').map(|p| {
+            let (p, hit) = match p.strip_suffix('*') {
+                Some(p) => (p, true),
+                None => (p, false),
+            };
+
+            let point = match p.to_ascii_lowercase().as_str() {
+                "bar" => BAR_POINT,
+                "off" => OFF_POINT,
+                p => p.parse().ok().filter(|&p| p <= BAR_POINT)?,
+            };
+
+            Some((point, hit))
+        });
+
+        let (mut from, _) = points.next().flatten().ok_or_else(syntax_err)?;
+        let mut path_moves = Vec::new();
+
+        for point in points {
+            let (to, hit) = point.ok_or_else(syntax_err)?;
+            if to >= from || from == OFF_POINT || to == BAR_POINT {
+                return Err(syntax_err());
+            }
+
+            path_moves.push(NotatedMove { from, to, hit });
+            from = to;
+        }
+
+        if path_moves.is_empty() {
+            return Err(syntax_err());
+        }
+
+        for _ in 0..count {
+            moves.extend_from_slice(&path_moves);
+        }
+
+        if moves.len() > 4 {
+            return Err(NotationError::TooManyMoves);
+        }
+    }
+
+    Ok(moves)
+}
+
+/// Finds the play `text` describes for `turn` rolling `dice`, checking it
+/// against the plays [`MoveBuffer::generate`] allows.
+///
+/// Moves may be listed in any order and a move spanning several dice, like
+/// `13/8` for a 3-2, is split as needed. If the dice can be split in more
+/// than one way with different results, the `*` marks pick the one with that
+/// many hits.
+pub fn parse_play(
+    state: GameState,
+    turn: bool,
+    dice: [u8; 2],
+    text: &str,
+) -> Result<(Play, GameState), NotationError> {
+    let moves = parse_moves(text)?;
+
+    let available = if dice[0] == dice[1] {
+        vec![dice[0]; 4]
+    } else {
+        dice.to_vec()
+    };
+
+    let mut candidates = Vec::new();
+    let mut first_err = None;
+
+    for order in permutations(moves.len()) {
+        let ordered: Vec<_> = order.iter().map(|&i| moves[i]).collect();
+
+        realize(
+            state,
+            turn,
+            &available,
+            &ordered,
+            &mut Play::new(),
+            &mut first_err,
+            &mut candidates,
+        );
+    }
+
+    if candidates.is_empty() {
+        return Err(NotationError::Unplayable(first_err));
+    }
+
+    let mut buffer = MoveBuffer::new();
+    buffer.generate(turn, state, dice);
+    let legal: Vec<_> = buffer.state_iterator().collect();
+
+    candidates.retain(|(_, s)| legal.contains(s));
+
+    // Hits by `turn` go to the opponent's bar, which is at index `turn`.
+    let marked_hits = moves.iter().filter(|m| m.hit).count();
+    let hits = |s: &GameState| {
+        (s.captured[turn as usize] - state.captured[turn as usize]) as usize
+    };
+
+    candidates
+        .iter()
+        .find(|(_, s)| hits(s) == marked_hits)
+        .or_else(|| candidates.first())
+        .cloned()
+        .ok_or(NotationError::Illegal)
+}
+
+/// Formats a play given as `[from, die]` steps, merging steps of the same
+/// checker and counting repeated moves.
+///
+/// # Panics
+///
+/// If a step is not a legal move.
+pub fn format_play(
+    mut state: GameState,
+    turn: bool,
+    play: &[[u8; 2]],
+) -> String {
+    // Start point and the (point, hit) landings of each moved checker.
+    let mut paths: Vec<(u8, Vec<(u8, bool)>)> = Vec::new();
+
+    for &[from, die] in play {
+        let from_point = point_number(turn, from);
+        let to_point = from_point.saturating_sub(die);
+
+        let opponent_blot = if turn { -1 } else { 1 };
+        let hit = point_index(turn, to_point)
+            .is_some_and(|i| state.tiles[i as usize] == opponent_blot);
+
+        state = state.do_move(turn, from, die).expect("Illegal play");
+
+        // A checker moving on continues its path, which only shows the points
+        // in between where it hit.
+        match paths
+            .iter_mut()
+            .rev()
+            .find(|(_, landings)| landings.last().unwrap().0 == from_point)
+        {
+            Some((_, landings)) => {
+                if !landings.last().unwrap().1 {
+                    landings.pop();
+                }
+                landings.push((to_point, hit));
+            }
+            None => paths.push((from_point, vec![(to_point, hit)])),
+        }
+    }
+
+    paths.sort_by(|(from1, path1), (from2, path2)| {
+        (from2, path2.last().unwrap().0).cmp(&(from1, path1.last().unwrap().0))
+    });
+
+    let formatted: Vec<_> = paths
+        .iter()
+        .map(|(from, landings)| {
+            let mut s = format_point(*from);
+            for &(to, hit) in landings {
+                s.push('/');
+                s.push_str(&format_point(to));
+                if hit {
+                    s.push('*');
+                }
+            }
+            s
+        })
+        .collect();
+
+    let mut parts: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < formatted.len() {
+        let n = formatted[i..]
+            .iter()
+            .take_while(|&s| s == &formatted[i])
+            .count();
+
+        if n == 1 {
+            parts.push(formatted[i].clone());
+        } else {
+            parts.push(format!("{}({n})", formatted[i]));
+        }
+
+        i += n;
+    }
+
+    parts.join(" ")
+}
+
+fn format_point(point: u8) -> String {
+    match point {
+        BAR_POINT => "bar".to_owned(),
+        OFF_POINT => "off".to_owned(),
+        p => p.to_string(),
+    }
+}
+
+// Tries every way of playing `moves` in order with the remaining `dice`,
+// pushing each complete play onto `out`.
+fn realize(
+    state: GameState,
+    turn: bool,
+    dice: &[u8],
+    moves: &[NotatedMove],
+    play: &mut Play,
+    first_err: &mut Option<MoveError>,
+    out: &mut Vec<(Play, GameState)>,
+) {
+    let Some((&m, rest)) = moves.split_first() else {
+        out.push((play.clone(), state));
+        return;
+    };
+
+    let mut arrivals = Vec::new();
+    walk(
+        state,
+        turn,
+        m.from,
+        m.to,
+        dice,
+        play,
+        first_err,
+        &mut arrivals,
+    );
+
+    for (state, dice, mut play) in arrivals {
+        realize(state, turn, &dice, rest, &mut play, first_err, out);
+    }
+}
+
+// Moves one checker from `from` towards `to` using any sequence of the
+// remaining dice, collecting the position, unused dice and play for every
+// way of arriving.
+#[allow(clippy::too_many_arguments)]
+fn walk(
+    state: GameState,
+    turn: bool,
+    from: u8,
+    to: u8,
+    dice: &[u8],
+    play: &mut Play,
+    first_err: &mut Option<MoveError>,
+    arrivals: &mut Vec<(GameState, Vec<u8>, Play)>,
+) {
+    for (i, &die) in dice.iter().enumerate() {
+        // Doubles only need one attempt per step.
+        if dice[..i].contains(&die) {
+            continue;
+        }
+
+        let landing = from.saturating_sub(die);
+        if landing < to || landing == OFF_POINT && to != OFF_POINT {
+            continue;
+        }
+
+        let index = point_index(turn, from).unwrap();
+        let new_state = match state.do_move(turn, index, die) {
+            Ok(s) => s,
+            Err(e) => {
+                first_err.get_or_insert(e);
+                continue;
+            }
+        };
+
+        let mut rest = dice.to_vec();
+        rest.remove(i);
+
+        play.push([index, die]);
+        if landing == to {
+            arrivals.push((new_state, rest, play.clone()));
+        } else {
+            walk(
+                new_state, turn, landing, to, &rest, play, first_err, arrivals,
+            );
+        }
+        play.pop();
+    }
+}
+
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for p in permutations(n - 1) {
+        for i in 0..n {
+            let mut p = p.clone();
+            p.insert(i, n - 1);
+            result.push(p);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,136 @@
+use std::{error::Error, fmt::Display};
+
+use crate::base64;
+
+use super::{notation::point_index, GameState, SPECIAL_MOVE};
+
+/// Why a GNU Backgammon Position ID could not be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionIdError {
+    /// The ID is not 14 characters long.
+    Length(usize),
+    /// A character outside the base64 alphabet.
+    Character(char),
+    /// The key runs out before describing both players.
+    Truncated,
+    /// `player` has more than 15 checkers.
+    TooManyCheckers { player: bool },
+    /// Both players have checkers on the point at `index`.
+    SharedPoint { index: u8 },
+}
+
+impl Display for PositionIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Length(len) => {
+                write!(f, "Position ID has {len} characters instead of 14")
+            }
+            Self::Character(c) => {
+                write!(f, "Invalid character {c:?} in position ID")
+            }
+            Self::Truncated => write!(f, "Position ID is missing checkers"),
+            Self::TooManyCheckers { player } => {
+                let player = if player { "Light" } else { "Dark" };
+                write!(f, "{player} has more than 15 checkers")
+            }
+            Self::SharedPoint { index } => {
+                write!(f, "Both players have checkers on {index}")
+            }
+        }
+    }
+}
+
+impl Error for PositionIdError {}
+
+impl GameState {
+    /// The GNU Backgammon Position ID of this position with `turn` on roll.
+    ///
+    /// The 80 bit key lists the checkers of the player not on roll, then of
+    /// the player on roll, each from their own ace point to the bar. Panics if
+    /// either player has more than 15 checkers.
+    pub fn to_position_id(&self, turn: bool) -> String {
+        let mut key = [0u8; 10];
+        let mut bit = 0;
+
+        for player in [!turn, turn] {
+            for point in 1..=25 {
+                for _ in 0..self.count(player, point) {
+                    key[bit / 8] |= 1 << (bit % 8);
+                    bit += 1;
+                }
+                bit += 1;
+            }
+        }
+
+        base64::encode(&key)
+    }
+
+    /// Reads a GNU Backgammon Position ID, with `turn` being the player on
+    /// roll. Checkers missing from the key are borne off.
+    pub fn from_position_id(
+        id: &str,
+        turn: bool,
+    ) -> Result<Self, PositionIdError> {
+        let len = id.chars().count();
+        if len != 14 {
+            return Err(PositionIdError::Length(len));
+        }
+
+        let mut key = [0u8; 11];
+        base64::decode(id, &mut key).map_err(PositionIdError::Character)?;
+
+        // The key fills each byte from the least significant bit.
+        let mut bits = (0..80).map(|bit| key[bit / 8] & (1 << (bit % 8)) != 0);
+        let mut state = Self::new();
+
+        for player in [!turn, turn] {
+            let mut total = 0;
+
+            for point in 1..=25 {
+                let mut n = 0;
+                while bits.next().ok_or(PositionIdError::Truncated)? {
+                    n += 1;
+                    if total + n > 15 {
+                        return Err(PositionIdError::TooManyCheckers {
+                            player,
+                        });
+                    }
+                }
+                total += n;
+
+                match point_index(player, point).unwrap() {
+                    SPECIAL_MOVE => state.captured[(!player) as usize] = n,
+                    index => {
+                        let tile = &mut state.tiles[index as usize];
+                        if *tile != 0 && n != 0 {
+                            return Err(PositionIdError::SharedPoint { index });
+                        }
+                        if n != 0 {
+                            *tile = if player { n as i8 } else { -(n as i8) };
+                        }
+                    }
+                }
+            }
+
+            state.finished[(!player) as usize] = 15 - total;
+        }
+
+        Ok(state)
+    }
+
+    // Checkers `player` has on `point`, numbered from their side with the
+    // bar as 25.
+    fn count(&self, player: bool, point: u8) -> u8 {
+        match point_index(player, point).unwrap() {
+            SPECIAL_MOVE => self.captured[(!player) as usize],
+            index => {
+                let n = self.tiles[index as usize];
+                if player {
+                    n.max(0) as u8
+                } else {
+                    (-n).max(0) as u8
+                }
+            }
+        }
+    }
+}
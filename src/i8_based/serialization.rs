@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::backgammon::{Backgammon, PositionError};
+
+use super::GameState;
+
+// How a `GameState` is serialized, checked on the way in.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Position {
+    checkers: [i8; 24],
+    bar: [u8; 2],
+    borne_off: [u8; 2],
+}
+
+impl From<GameState> for Position {
+    fn from(state: GameState) -> Self {
+        Self {
+            checkers: state.tiles,
+            bar: state.captured,
+            borne_off: state.finished,
+        }
+    }
+}
+
+impl TryFrom<Position> for GameState {
+    type Error = PositionError;
+
+    fn try_from(position: Position) -> Result<Self, PositionError> {
+        let state = Self::from_parts(
+            position.checkers,
+            position.bar,
+            position.borne_off,
+        );
+        state.validate()?;
+        Ok(state)
+    }
+}
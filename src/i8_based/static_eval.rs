@@ -0,0 +1,499 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::{probabilities::Probabilities, rng::Rng};
+
+use super::GameState;
+
+// Mean and standard deviation of the pips moved by one roll.
+const ROLL_MEAN: f64 = 49.0 / 6.0;
+const ROLL_SD: f64 = 4.3;
+
+/// Scores the positions at the leaves of an
+/// [`Evaluator`](super::Evaluator)'s search.
+pub trait StaticEval {
+    /// The chances of `turn`, about to roll, in `state`, which is not
+    /// finished.
+    fn eval(&self, state: &GameState, turn: bool) -> Probabilities;
+}
+
+impl<E: StaticEval + ?Sized> StaticEval for &E {
+    fn eval(&self, state: &GameState, turn: bool) -> Probabilities {
+        (**self).eval(state, turn)
+    }
+}
+
+// A player's checkers by their distance from being borne off: 0 is borne
+// off and 25 is the bar.
+type Board = [u32; 26];
+
+// The boards of the player on roll and of their opponent. The opponent's
+// checkers on the same point as the player's at distance `d` are at `25 - d`.
+fn boards(state: &GameState, turn: bool) -> [Board; 2] {
+    [turn, !turn].map(|player| {
+        // Light is index 0.
+        let side = (!player) as usize;
+
+        let mut board = [0; 26];
+        board[0] = state.finished[side] as u32;
+        board[25] = state.captured[side] as u32;
+        for (i, &t) in state.tiles.iter().enumerate() {
+            match (player, t) {
+                (true, t) if t > 0 => board[24 - i] = t as u32,
+                (false, t) if t < 0 => board[i + 1] = -t as u32,
+                _ => {}
+            }
+        }
+        board
+    })
+}
+
+fn pips(board: &Board) -> u32 {
+    board.iter().enumerate().map(|(d, &n)| d as u32 * n).sum()
+}
+
+// The distance of the checker furthest from home.
+fn rearmost(board: &Board) -> usize {
+    (1..26).rev().find(|&d| board[d] > 0).unwrap_or(0)
+}
+
+// Whether the players' checkers have passed each other, so neither can hit
+// the other again.
+fn is_race(boards: &[Board; 2]) -> bool {
+    rearmost(&boards[0]) + rearmost(&boards[1]) <= 25
+}
+
+// The chance of finishing first with a `lead` in pips, when both players
+// together have `pips` left to move. Both totals vary with the number of
+// rolls, which is approximated with a normal distribution.
+fn race_chance(lead: f64, pips: f64) -> f64 {
+    let rolls = pips / ROLL_MEAN;
+    let sd = ROLL_SD * rolls.max(1.0).sqrt();
+
+    // Logistic approximation of the normal distribution.
+    1.0 / (1.0 + (-1.702 * lead / sd).exp())
+}
+
+// The chances of the player on roll treating the position as a race, with
+// `pips` the effective pip counts of both players.
+//
+// Each player is expected to move the same number of pips per roll, so the
+// lead in pips, with half a roll for being on roll, gives the chance of
+// winning. Once there is no contact, gammons and backgammons are races in
+// the same way, against the pips the loser needs to bear off a checker or to
+// leave the winner's home board. Before that the pip count says too little
+// about them, and they are left out.
+fn race(boards: &[Board; 2], pips: [f64; 2]) -> Probabilities {
+    let race = is_race(boards);
+
+    // The chances of `boards[winner]` winning a gammon and a backgammon.
+    let gammons = |winner: usize| {
+        let loser = &boards[1 - winner];
+        if !race || loser[0] > 0 {
+            return [0.0; 2];
+        }
+
+        let on_roll = if winner == 0 { 0.5 } else { -0.5 } * ROLL_MEAN;
+        let winner = pips[winner];
+
+        let mut save = 0;
+        let mut nearest = 6;
+        let mut escape = 0;
+        for (d, &n) in loser.iter().enumerate().filter(|&(_, &n)| n > 0) {
+            let d = d as u32;
+            save += n * d.saturating_sub(6);
+            nearest = nearest.min(d);
+            escape += n * d.saturating_sub(18);
+        }
+        let save = (save + nearest) as f64;
+        let escape = escape as f64;
+
+        let gammon = race_chance(save - winner + on_roll, save + winner);
+        let backgammon = if escape > 0.0 {
+            race_chance(escape - winner + on_roll, escape + winner)
+        } else {
+            0.0
+        };
+
+        [gammon, backgammon.min(gammon)]
+    };
+
+    let lead = pips[1] - pips[0] + 0.5 * ROLL_MEAN;
+    let win = race_chance(lead, pips[0] + pips[1]);
+    let [win_gammon, win_backgammon] = gammons(0).map(|p| p.min(win));
+    let [lose_gammon, lose_backgammon] = gammons(1).map(|p| p.min(1.0 - win));
+
+    Probabilities {
+        win,
+        win_gammon,
+        win_backgammon,
+        lose_gammon,
+        lose_backgammon,
+    }
+}
+
+/// Treats every position as a race decided by the pip count, with gammons
+/// once the checkers have passed each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PipCount;
+
+impl StaticEval for PipCount {
+    fn eval(&self, state: &GameState, turn: bool) -> Probabilities {
+        let boards = boards(state, turn);
+        race(&boards, boards.each_ref().map(|b| pips(b) as f64))
+    }
+}
+
+/// Like [`PipCount`], but races are scored with Keith's adjustments for the
+/// pips a player will waste bearing off: 2 for each checker past the first
+/// on the 1 point, 1 for each past the first on the 2 point and past the
+/// third on the 3 point, and 1 for each of the 4, 5 and 6 points left empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RaceFormula;
+
+impl RaceFormula {
+    fn count(board: &Board) -> u32 {
+        let extra = |d: usize, keep: u32| board[d].saturating_sub(keep);
+        let gaps = (4..=6).filter(|&d| board[d] == 0).count() as u32;
+
+        pips(board) + 2 * extra(1, 1) + extra(2, 1) + extra(3, 3) + gaps
+    }
+}
+
+impl StaticEval for RaceFormula {
+    fn eval(&self, state: &GameState, turn: bool) -> Probabilities {
+        let boards = boards(state, turn);
+        let count = if is_race(&boards) { Self::count } else { pips };
+
+        race(&boards, boards.each_ref().map(|b| count(b) as f64))
+    }
+}
+
+/// A hand-tuned evaluation of positions with contact, adding features of
+/// each player's checkers to their pip count lead. The weights are in pips;
+/// races are left to [`RaceFormula`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Heuristic {
+    /// Per single checker the opponent has a checker behind.
+    pub blot: f64,
+    /// Per point made in the player's home board or on their bar point.
+    pub point: f64,
+    /// Per point in the longest run of made points past the second.
+    pub prime: f64,
+    /// Per point made in the opponent's home board, up to two.
+    pub anchor: f64,
+}
+
+impl Default for Heuristic {
+    fn default() -> Self {
+        Self {
+            blot: -4.0,
+            point: 5.0,
+            prime: 4.0,
+            anchor: 6.0,
+        }
+    }
+}
+
+impl Heuristic {
+    fn score(&self, board: &Board, opponent: &Board) -> f64 {
+        let made = |d: usize| board[d] >= 2;
+        let behind = rearmost(opponent);
+
+        let blots = (1..=24)
+            .filter(|&d| board[d] == 1 && behind > 25 - d)
+            .count();
+        let points = (1..=7).filter(|&d| made(d)).count();
+        let anchors = (19..=24).filter(|&d| made(d)).count().min(2);
+
+        let mut prime: usize = 0;
+        let mut run = 0;
+        for d in 1..=24 {
+            run = if made(d) { run + 1 } else { 0 };
+            prime = prime.max(run);
+        }
+
+        self.blot * blots as f64
+            + self.point * points as f64
+            + self.prime * prime.saturating_sub(2) as f64
+            + self.anchor * anchors as f64
+    }
+}
+
+impl StaticEval for Heuristic {
+    fn eval(&self, state: &GameState, turn: bool) -> Probabilities {
+        let boards = boards(state, turn);
+        if is_race(&boards) {
+            return RaceFormula.eval(state, turn);
+        }
+
+        let [board, opponent] = &boards;
+        let [mine, theirs] = boards.each_ref().map(|b| pips(b) as f64);
+        let features =
+            self.score(board, opponent) - self.score(opponent, board);
+        let lead = theirs - mine + 0.5 * ROLL_MEAN + features;
+
+        Probabilities::single(race_chance(lead, mine + theirs))
+    }
+}
+
+/// A neural network with one hidden layer, in the style of TD-Gammon.
+///
+/// The inputs describe the board from the side of the player on roll: for
+/// each point and each player, whether there are at least one, two and three
+/// checkers and how many more there are, then the checkers on the bar and
+/// borne off. The five outputs are the [`Probabilities`].
+///
+/// A new network plays no better than chance. It learns with
+/// [`train`](Self::train), e.g. by temporal difference learning in self-play
+/// as `cargo run --release --example train` does, and its weights are saved
+/// and loaded in a text form.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeuralNet {
+    // A bias followed by a weight per input, for each hidden unit.
+    hidden: Vec<Vec<f64>>,
+    // A bias followed by a weight per hidden unit, for each output.
+    outputs: Vec<Vec<f64>>,
+}
+
+impl NeuralNet {
+    pub const INPUTS: usize = 2 * (24 * 4 + 2);
+    pub const OUTPUTS: usize = 5;
+
+    /// A network with `hidden` units and small random weights.
+    pub fn new(hidden: usize, rng: &mut Rng) -> Self {
+        let mut layer = |rows: usize, inputs: usize| {
+            let scale = 1.0 / (inputs as f64).sqrt();
+            (0..rows)
+                .map(|_| {
+                    (0..=inputs)
+                        .map(|_| scale * (2.0 * rng.next_f64() - 1.0))
+                        .collect()
+                })
+                .collect()
+        };
+
+        Self {
+            hidden: layer(hidden, Self::INPUTS),
+            outputs: layer(Self::OUTPUTS, hidden),
+        }
+    }
+
+    /// The number of hidden units.
+    pub fn hidden(&self) -> usize {
+        self.hidden.len()
+    }
+
+    /// Moves the outputs for `state`, with `turn` about to roll, towards
+    /// `target` by one step of gradient descent with `rate`.
+    pub fn train(
+        &mut self,
+        state: &GameState,
+        turn: bool,
+        target: &Probabilities,
+        rate: f64,
+    ) {
+        let inputs = Self::inputs(state, turn);
+        let (hidden, outputs) = self.forward(&inputs);
+        let target = [
+            target.win,
+            target.win_gammon,
+            target.win_backgammon,
+            target.lose_gammon,
+            target.lose_backgammon,
+        ];
+
+        // Squared error through the sigmoids.
+        let deltas: Vec<f64> = outputs
+            .iter()
+            .zip(target)
+            .map(|(o, t)| (o - t) * o * (1.0 - o))
+            .collect();
+
+        for (j, &h) in hidden.iter().enumerate() {
+            let back: f64 = (0..Self::OUTPUTS)
+                .map(|k| deltas[k] * self.outputs[k][j + 1])
+                .sum();
+            let delta = back * h * (1.0 - h);
+
+            let weights = &mut self.hidden[j];
+            weights[0] -= rate * delta;
+            for (w, &x) in weights[1..].iter_mut().zip(&inputs) {
+                *w -= rate * delta * x;
+            }
+        }
+
+        for (weights, &delta) in self.outputs.iter_mut().zip(&deltas) {
+            weights[0] -= rate * delta;
+            for (w, &h) in weights[1..].iter_mut().zip(&hidden) {
+                *w -= rate * delta * h;
+            }
+        }
+    }
+
+    fn inputs(state: &GameState, turn: bool) -> Vec<f64> {
+        let mut inputs = Vec::with_capacity(Self::INPUTS);
+
+        for board in boards(state, turn) {
+            for &n in &board[1..25] {
+                inputs.extend([n >= 1, n >= 2, n >= 3].map(f64::from));
+                inputs.push(n.saturating_sub(3) as f64 / 2.0);
+            }
+            inputs.push(board[25] as f64 / 2.0);
+            inputs.push(board[0] as f64 / 15.0);
+        }
+
+        inputs
+    }
+
+    // The hidden and output activations.
+    fn forward(&self, inputs: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        let layer = |weights: &[Vec<f64>], inputs: &[f64]| -> Vec<f64> {
+            weights
+                .iter()
+                .map(|w| {
+                    let products =
+                        w[1..].iter().zip(inputs).map(|(w, x)| w * x);
+                    let sum = w[0] + products.sum::<f64>();
+                    1.0 / (1.0 + (-sum).exp())
+                })
+                .collect()
+        };
+
+        let hidden = layer(&self.hidden, inputs);
+        let outputs = layer(&self.outputs, &hidden);
+        (hidden, outputs)
+    }
+}
+
+impl StaticEval for NeuralNet {
+    fn eval(&self, state: &GameState, turn: bool) -> Probabilities {
+        let (_, outputs) = self.forward(&Self::inputs(state, turn));
+
+        // Each chance is part of the one before it.
+        let win = outputs[0];
+        let win_gammon = outputs[1].min(win);
+        let lose_gammon = outputs[3].min(1.0 - win);
+
+        Probabilities {
+            win,
+            win_gammon,
+            win_backgammon: outputs[2].min(win_gammon),
+            lose_gammon,
+            lose_backgammon: outputs[4].min(lose_gammon),
+        }
+    }
+}
+
+/// Writes the text form read by [`str::parse`]: a header line with the
+/// number of inputs and hidden units, then one line of weights per hidden
+/// unit and per output, each starting with its bias.
+impl Display for NeuralNet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "neural-net {} {}", Self::INPUTS, self.hidden())?;
+        for weights in self.hidden.iter().chain(&self.outputs) {
+            let weights: Vec<_> =
+                weights.iter().map(|w| w.to_string()).collect();
+            writeln!(f, "{}", weights.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NeuralNet {
+    type Err = NetError;
+
+    fn from_str(text: &str) -> Result<Self, NetError> {
+        let lines: Vec<_> = text.lines().collect();
+        let missing = |n| NetError::new(n, NetErrorKind::Missing);
+
+        let header = lines.first().ok_or(missing(1))?;
+        let hidden = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            ["neural-net", inputs, hidden]
+                if inputs.parse::<usize>() == Ok(Self::INPUTS) =>
+            {
+                hidden.parse().ok()
+            }
+            _ => None,
+        }
+        .ok_or_else(|| {
+            NetError::new(1, NetErrorKind::Syntax(header.to_string()))
+        })?;
+
+        // The `i`th row of weights after the header, which has a bias and
+        // `expected` weights.
+        let row = |i: usize, expected: usize| {
+            let n = i + 2;
+            let line = lines.get(n - 1).ok_or(missing(n))?;
+
+            let weights = line
+                .split_whitespace()
+                .map(|w| {
+                    w.parse().map_err(|_| {
+                        NetError::new(n, NetErrorKind::Syntax(w.into()))
+                    })
+                })
+                .collect::<Result<Vec<f64>, _>>()?;
+
+            if weights.len() != expected + 1 {
+                return Err(NetError::new(
+                    n,
+                    NetErrorKind::RowLength {
+                        expected: expected + 1,
+                        found: weights.len(),
+                    },
+                ));
+            }
+            Ok(weights)
+        };
+
+        Ok(Self {
+            hidden: (0..hidden)
+                .map(|i| row(i, Self::INPUTS))
+                .collect::<Result<_, _>>()?,
+            outputs: (0..Self::OUTPUTS)
+                .map(|k| row(hidden + k, hidden))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetError {
+    /// Numbered from 1.
+    pub line: usize,
+    pub kind: NetErrorKind,
+}
+
+impl NetError {
+    fn new(line: usize, kind: NetErrorKind) -> Self {
+        Self { line, kind }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetErrorKind {
+    /// A header or weight that could not be parsed.
+    Syntax(String),
+    /// A row with the wrong number of weights, counting the bias.
+    RowLength { expected: usize, found: usize },
+    /// The text ends before all the weights.
+    Missing,
+}
+
+impl Display for NetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+
+        match &self.kind {
+            NetErrorKind::Syntax(text) => write!(f, "Could not parse {text:?}"),
+            NetErrorKind::RowLength { expected, found } => {
+                write!(f, "Expected {expected} weights in a row, found {found}")
+            }
+            NetErrorKind::Missing => write!(f, "Expected more weights"),
+        }
+    }
+}
+
+impl Error for NetError {}
@@ -0,0 +1,90 @@
+//! Backgammon rules, move generation and position evaluation.
+//!
+//! The main engine lives in [`i8_based`] and its core types are re-exported
+//! at the crate root:
+//!
+//! ```
+//! use backgammon_sim::{GameState, MoveBuffer};
+//!
+//! let state = GameState::new_with_default_setup();
+//!
+//! let mut moves = MoveBuffer::new();
+//! moves.generate(true, state, [3, 1]);
+//!
+//! for new_state in moves.state_iterator() {
+//!     println!("{new_state}");
+//! }
+//! ```
+//!
+//! Whole games are played by [`game::play_game`] between [`game::Player`]
+//! implementations, with dice from any [`dice::DiceSource`].
+//!
+//! [`enum_based`] is an independent implementation of the same rules. Code
+//! that should work with either can be written against the [`Backgammon`]
+//! trait.
+//!
+//! # Serialization
+//!
+//! With the `serde` feature, positions, plays and game records implement
+//! `Serialize` and `Deserialize`. In JSON, players are booleans with `true`
+//! for Light, and pairs indexed by player are `[light, dark]`.
+//!
+//! A [`GameState`] is the number of checkers on each point, positive for
+//! Light and negative for Dark, indexed as in [`GameState::do_move`]. Positions
+//! without exactly 15 checkers per player are rejected.
+//!
+//! ```json
+//! {
+//!   "checkers": [2, 0, 0, 0, 0, -5, 0, -3, 0, 0, 0, 5,
+//!                -5, 0, 0, 0, 3, 0, 5, 0, 0, 0, 0, -2],
+//!   "bar": [0, 0],
+//!   "borne_off": [0, 0]
+//! }
+//! ```
+//!
+//! A [`Play`] is a list of `[from, die]` steps, with [`SPECIAL_MOVE`] (99) as
+//! `from` for the bar, and dice are `[d1, d2]`. A [`game::GameRecord`] holds
+//! each [`game::Turn`] with the position after it:
+//!
+//! ```json
+//! {
+//!   "start": { "checkers": [...], "bar": [0, 0], "borne_off": [0, 0] },
+//!   "turns": [
+//!     {
+//!       "turn": true,
+//!       "dice": [3, 1],
+//!       "play": [[16, 3], [18, 1]],
+//!       "state": { "checkers": [...], "bar": [0, 0], "borne_off": [0, 0] }
+//!     }
+//!   ],
+//!   "result": { "winner": true, "kind": "gammon" }
+//! }
+//! ```
+//!
+//! [`WinKind`] is one of `"single"`, `"gammon"` or `"backgammon"`.
+//! [`game::Action`] is an object with a single key: `{"roll": turn}`,
+//! `{"double": {"player": true, "value": 2}}`, `{"take": false}`,
+//! `{"drop": false}` or `{"beaver": false}`. [`match_state::MatchState`] has
+//! the fields of the struct, with `"phase"` in snake case and `"dice"` and
+//! `"cube_owner"` `null` when unset. A [`cube::Cube`] is
+//! `{"value": 2, "owner": false}`, with a `null` owner while centred.
+
+pub mod backgammon;
+mod base64;
+pub mod cube;
+pub mod dice;
+pub mod differential;
+pub mod enum_based;
+pub mod game;
+pub mod i8_based;
+pub mod match_state;
+pub mod met;
+pub mod probabilities;
+pub mod render;
+pub mod rng;
+
+pub use backgammon::{Backgammon, GameResult, PositionError, WinKind};
+pub use i8_based::{
+    Evaluator, GameState, MoveBuffer, MoveError, Play, PlayIterator,
+    StateIterator, SPECIAL_MOVE,
+};
@@ -1,5 +1,65 @@
-mod i8_based;
+use std::{env, process};
+
+use backgammon_sim::{dice::SeededDice, game::Repl, render::Theme};
+
+const USAGE: &str = "\
+Usage: backgammon-sim [--dark] [--depth N] [--seed N] [--theme THEME]
+
+Play a game against the engine. Type `help` during the game for commands.
+
+  --dark         play Dark instead of Light
+  --depth N      plies the engine searches (default 2)
+  --seed N       seed for the dice (default: the current time)
+  --theme THEME  ansi, unicode or ascii (default ansi)";
 
 fn main() {
-    i8_based::_test4();
+    let mut repl = Repl::stdio();
+    let mut dice = SeededDice::from_time();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .unwrap_or_else(|| exit(&format!("{name} needs a value")))
+        };
+
+        match arg.as_str() {
+            "--dark" => repl = repl.with_side(false),
+            "--depth" => {
+                let depth = value("--depth")
+                    .parse()
+                    .ok()
+                    .filter(|&d| d > 0)
+                    .unwrap_or_else(|| exit("Depth must be at least 1"));
+                repl = repl.with_depth(depth);
+            }
+            "--seed" => {
+                let seed = value("--seed")
+                    .parse()
+                    .unwrap_or_else(|_| exit("Seed must be a number"));
+                dice = SeededDice::new(seed);
+            }
+            "--theme" => {
+                let theme: Theme = value("--theme")
+                    .parse()
+                    .unwrap_or_else(|e: String| exit(&e));
+                repl = repl.with_theme(theme);
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ => exit(&format!("Unknown argument {arg:?}")),
+        }
+    }
+
+    println!("Dice seed {}", dice.seed());
+    if let Err(e) = repl.play(&mut dice) {
+        exit(&format!("{e}"));
+    }
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{message}\n\n{USAGE}");
+    process::exit(2);
 }
@@ -0,0 +1,275 @@
+//! The full context of a position within a match or money session, and its
+//! GNU Backgammon Match ID.
+//!
+//! GNU Backgammon numbers the players 0 and 1. Here player 0 is Light and
+//! player 1 is Dark, so per-player arrays stay indexed [light, dark].
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::{
+    backgammon::WinKind,
+    base64,
+    i8_based::{GameState, PositionIdError},
+};
+
+/// Where a game stands, as recorded in the Match ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum GamePhase {
+    NoGame,
+    Playing,
+    Over,
+    Resigned,
+    /// Ended by refusing a double.
+    DroppedCube,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchState {
+    pub position: GameState,
+    /// A power of two from 1 to 2^15.
+    pub cube_value: u32,
+    /// `None` while the cube is centred.
+    pub cube_owner: Option<bool>,
+    /// The player rolling the dice this turn.
+    pub on_roll: bool,
+    /// The player who has to act next. Differs from `on_roll` while a double
+    /// or resignation is being considered.
+    pub turn: bool,
+    pub crawford: bool,
+    pub phase: GamePhase,
+    pub double_offered: bool,
+    pub resignation: Option<WinKind>,
+    /// `None` before the dice are rolled.
+    pub dice: Option<[u8; 2]>,
+    /// Zero for money play.
+    pub match_length: u16,
+    pub score: [u16; 2],
+}
+
+impl MatchState {
+    /// A game in progress at `position` with `on_roll` to roll, a centred
+    /// cube and no score.
+    pub fn new(position: GameState, on_roll: bool, match_length: u16) -> Self {
+        Self {
+            position,
+            cube_value: 1,
+            cube_owner: None,
+            on_roll,
+            turn: on_roll,
+            crawford: false,
+            phase: GamePhase::Playing,
+            double_offered: false,
+            resignation: None,
+            dice: None,
+            match_length,
+            score: [0, 0],
+        }
+    }
+
+    /// The 12 character GNU Backgammon Match ID.
+    pub fn match_id(&self) -> String {
+        let mut key = Key::default();
+
+        key.push(self.cube_value.trailing_zeros(), 4);
+        key.push(self.cube_owner.map_or(3, player_number), 2);
+        key.push(player_number(self.on_roll), 1);
+        key.push(self.crawford as u32, 1);
+        key.push(self.phase as u32, 3);
+        key.push(player_number(self.turn), 1);
+        key.push(self.double_offered as u32, 1);
+        key.push(self.resignation.map_or(0, WinKind::points), 2);
+        let [d1, d2] = self.dice.unwrap_or([0, 0]);
+        key.push(d1 as u32, 3);
+        key.push(d2 as u32, 3);
+        key.push(self.match_length as u32, 15);
+        key.push(self.score[0] as u32, 15);
+        key.push(self.score[1] as u32, 15);
+
+        base64::encode(&key.bytes)
+    }
+
+    /// The position ID and Match ID joined by a colon, the form GNU
+    /// Backgammon shows and accepts.
+    pub fn gnubg_id(&self) -> String {
+        format!(
+            "{}:{}",
+            self.position.to_position_id(self.on_roll),
+            self.match_id()
+        )
+    }
+
+    /// Reads a Match ID, taking the checkers from `position_id`.
+    pub fn from_ids(
+        position_id: &str,
+        match_id: &str,
+    ) -> Result<Self, MatchIdError> {
+        let len = match_id.chars().count();
+        if len != 12 {
+            return Err(MatchIdError::Length(len));
+        }
+
+        let mut key = Key::default();
+        base64::decode(match_id, &mut key.bytes)
+            .map_err(MatchIdError::Character)?;
+
+        let cube_log = key.pop(4);
+        if cube_log > 15 {
+            return Err(MatchIdError::Cube(cube_log));
+        }
+
+        let cube_owner = match key.pop(2) {
+            3 => None,
+            2 => return Err(MatchIdError::CubeOwner),
+            n => Some(n == 0),
+        };
+        let on_roll = key.pop(1) == 0;
+        let crawford = key.pop(1) == 1;
+
+        let phase = match key.pop(3) {
+            0 => GamePhase::NoGame,
+            1 => GamePhase::Playing,
+            2 => GamePhase::Over,
+            3 => GamePhase::Resigned,
+            4 => GamePhase::DroppedCube,
+            n => return Err(MatchIdError::Phase(n)),
+        };
+
+        let turn = key.pop(1) == 0;
+        let double_offered = key.pop(1) == 1;
+        let resignation = match key.pop(2) {
+            0 => None,
+            1 => Some(WinKind::Single),
+            2 => Some(WinKind::Gammon),
+            _ => Some(WinKind::Backgammon),
+        };
+
+        let dice = match [key.pop(3), key.pop(3)] {
+            [0, 0] => None,
+            [d1 @ 1..=6, d2 @ 1..=6] => Some([d1 as u8, d2 as u8]),
+            [d1, d2] => return Err(MatchIdError::Dice([d1 as u8, d2 as u8])),
+        };
+
+        let match_length = key.pop(15) as u16;
+        let score = [key.pop(15) as u16, key.pop(15) as u16];
+
+        let position = GameState::from_position_id(position_id, on_roll)
+            .map_err(MatchIdError::Position)?;
+
+        Ok(Self {
+            position,
+            cube_value: 1 << cube_log,
+            cube_owner,
+            on_roll,
+            turn,
+            crawford,
+            phase,
+            double_offered,
+            resignation,
+            dice,
+            match_length,
+            score,
+        })
+    }
+}
+
+/// Parses the `position_id:match_id` form of [`MatchState::gnubg_id`].
+impl FromStr for MatchState {
+    type Err = MatchIdError;
+
+    fn from_str(s: &str) -> Result<Self, MatchIdError> {
+        let (position_id, match_id) =
+            s.trim().split_once(':').ok_or(MatchIdError::Separator)?;
+
+        Self::from_ids(position_id, match_id)
+    }
+}
+
+/// Why a Match ID could not be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchIdError {
+    /// The Match ID is not 12 characters long.
+    Length(usize),
+    /// A character outside the base64 alphabet.
+    Character(char),
+    /// The cube is larger than 2^15.
+    Cube(u32),
+    /// The cube owner is neither player nor centred.
+    CubeOwner,
+    /// An unknown game phase.
+    Phase(u32),
+    /// Dice that are not both rolled or both unrolled.
+    Dice([u8; 2]),
+    /// No `:` between the position ID and the Match ID.
+    Separator,
+    Position(PositionIdError),
+}
+
+impl Display for MatchIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Length(len) => {
+                write!(f, "Match ID has {len} characters instead of 12")
+            }
+            Self::Character(c) => {
+                write!(f, "Invalid character {c:?} in Match ID")
+            }
+            Self::Cube(log) => write!(f, "Cube value 2^{log} is too large"),
+            Self::CubeOwner => write!(f, "Invalid cube owner"),
+            Self::Phase(n) => write!(f, "Invalid game state {n}"),
+            Self::Dice([d1, d2]) => write!(f, "Invalid dice {d1} and {d2}"),
+            Self::Separator => {
+                write!(f, "Expected a position ID and Match ID joined by ':'")
+            }
+            Self::Position(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for MatchIdError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Position(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+fn player_number(player: bool) -> u32 {
+    if player {
+        0
+    } else {
+        1
+    }
+}
+
+// The 66 bits of a Match ID, each field stored least significant bit first.
+#[derive(Default)]
+struct Key {
+    bytes: [u8; 9],
+    bit: usize,
+}
+
+impl Key {
+    fn push(&mut self, value: u32, width: usize) {
+        for i in 0..width {
+            if value & (1 << i) != 0 {
+                self.bytes[self.bit / 8] |= 1 << (self.bit % 8);
+            }
+            self.bit += 1;
+        }
+    }
+
+    fn pop(&mut self, width: usize) -> u32 {
+        let mut value = 0;
+        for i in 0..width {
+            if self.bytes[self.bit / 8] & (1 << (self.bit % 8)) != 0 {
+                value |= 1 << i;
+            }
+            self.bit += 1;
+        }
+        value
+    }
+}
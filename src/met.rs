@@ -0,0 +1,390 @@
+//! Match equity tables: each player's chance of winning a match from a score,
+//! used to turn the chances of a game into match winning chances (MWC).
+//!
+//! Tables are read from text, with `#` starting a comment:
+//!
+//! ```text
+//! # Entries are percentages for the player needing the row's number of
+//! # points against an opponent needing the column's.
+//! name My table
+//! pre-crawford
+//! 50.00 67.90 75.19
+//! 32.10 50.00 59.61
+//! 24.81 40.39 50.00
+//! post-crawford
+//! 50.00 51.22 69.14
+//! ```
+//!
+//! The pre-Crawford rows are for 1, 2, 3... away, and the 1-away row is the
+//! Crawford game. The single post-Crawford row is for the player 1-away in the
+//! games after it. Published tables such as Kazaross XG2 or Rockwell-Kazaross
+//! can be copied into this form.
+//!
+//! Scores further from the end of the match than a table covers are treated
+//! as the furthest score it has.
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::{match_state::MatchState, probabilities::Probabilities};
+
+// The share of games before the Crawford game that [`MatchEquityTable::model`]
+// has end with a dropped double.
+const DROP_RATE: f64 = 0.3;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchEquityTable {
+    pub name: String,
+    // The chance of a player needing `i + 1` points against one needing
+    // `j + 1`, indexed [i][j].
+    pre_crawford: Vec<Vec<f64>>,
+    // The chance of the player needing 1 point against one needing `j + 1`
+    // after the Crawford game.
+    post_crawford: Vec<f64>,
+}
+
+/// The [`model`](MatchEquityTable::model) table up to 25 points with a
+/// quarter of the games ending in gammons.
+impl Default for MatchEquityTable {
+    fn default() -> Self {
+        Self::model(25, 0.25)
+    }
+}
+
+impl MatchEquityTable {
+    /// A table for matches up to `size` points, computed from a simple model
+    /// where each player wins half of the games and `gammon_rate` of the
+    /// games played out are gammons.
+    ///
+    /// Before the Crawford game, some games end with a dropped double and the
+    /// rest are played out on a 2-cube. The Crawford game has no cube, and
+    /// after it the trailer doubles at once.
+    ///
+    /// It is a reasonable stand-in, but a published table is more accurate.
+    pub fn model(size: u16, gammon_rate: f64) -> Self {
+        assert!(size > 0, "A table needs at least one row");
+
+        let n = size as usize;
+        let g = gammon_rate;
+
+        // The trailer's chance needing `k` points against the player 1-away
+        // after the Crawford game. The leader drops the trailer's double
+        // when that is better for them.
+        let mut trailer = vec![1.0; n + 1];
+        trailer[1] = 0.5;
+        for k in 2..=n {
+            let after = |points: usize| trailer[k.saturating_sub(points)];
+            let take = 0.5 * ((1.0 - g) * after(2) + g * after(4));
+            trailer[k] = take.min(trailer[k - 1]);
+        }
+
+        let mut pre_crawford = vec![vec![0.5; n]; n];
+        for j in 1..n {
+            // Winning the Crawford game wins the match.
+            let crawford = 0.5
+                + 0.5
+                    * ((1.0 - g) * (1.0 - trailer[j])
+                        + g * (1.0 - trailer[j - 1]));
+            pre_crawford[0][j] = crawford;
+            pre_crawford[j][0] = 1.0 - crawford;
+        }
+
+        for i in 1..n {
+            for j in 1..n {
+                // The chance after the player wins (`won`) or loses `points`.
+                let at = |points: usize, won: bool| {
+                    let (i, j) = if won {
+                        (i.checked_sub(points), Some(j))
+                    } else {
+                        (Some(i), j.checked_sub(points))
+                    };
+
+                    match (i, j) {
+                        (None, _) => 1.0,
+                        (_, None) => 0.0,
+                        (Some(i), Some(j)) => pre_crawford[i][j],
+                    }
+                };
+
+                let dropped = 0.5 * (at(1, true) + at(1, false));
+                let played = 0.5
+                    * ((1.0 - g) * (at(2, true) + at(2, false))
+                        + g * (at(4, true) + at(4, false)));
+
+                pre_crawford[i][j] =
+                    DROP_RATE * dropped + (1.0 - DROP_RATE) * played;
+            }
+        }
+
+        Self {
+            name: format!("Model with {:.0}% gammons", 100.0 * g),
+            pre_crawford,
+            post_crawford: trailer[1..].iter().map(|t| 1.0 - t).collect(),
+        }
+    }
+
+    /// The longest match the table covers.
+    pub fn size(&self) -> u16 {
+        self.pre_crawford.len() as u16
+    }
+
+    /// The chance of a player needing `away` points winning the match against
+    /// one needing `opponent_away`. `post_crawford` is whether the Crawford
+    /// game has been played.
+    pub fn get(
+        &self,
+        away: u16,
+        opponent_away: u16,
+        post_crawford: bool,
+    ) -> f64 {
+        self.at([away as i64, opponent_away as i64], post_crawford)
+    }
+
+    /// The chance of `player` winning the match in `state`, before the game
+    /// is played.
+    ///
+    /// # Panics
+    ///
+    /// For money play.
+    pub fn score_mwc(&self, state: &MatchState, player: bool) -> f64 {
+        let away = away(state, player);
+        let post_crawford = !state.crawford && away.contains(&1);
+
+        self.at(away, post_crawford)
+    }
+
+    /// The chance of `player` winning the match in `state` if the game ends
+    /// with the chances `probs` of `player` at the current cube value.
+    ///
+    /// # Panics
+    ///
+    /// For money play.
+    pub fn mwc(
+        &self,
+        state: &MatchState,
+        player: bool,
+        probs: &Probabilities,
+    ) -> f64 {
+        let [away, opponent_away] = away(state, player);
+        let cube = state.cube_value as i64;
+        let post_crawford =
+            state.crawford || [away, opponent_away].contains(&1);
+
+        probs
+            .outcomes()
+            .iter()
+            .map(|&(p, points)| {
+                let points = points as i64 * cube;
+                let after = if points > 0 {
+                    [away - points, opponent_away]
+                } else {
+                    [away, opponent_away + points]
+                };
+
+                p * self.at(after, post_crawford)
+            })
+            .sum()
+    }
+
+    /// [`mwc`](Self::mwc) scaled so that winning a single game at the current
+    /// cube value is 1 and losing one is -1, which makes it comparable to
+    /// money equities.
+    pub fn equity(
+        &self,
+        state: &MatchState,
+        player: bool,
+        probs: &Probabilities,
+    ) -> f64 {
+        let [win, lose] = [1.0, 0.0]
+            .map(|p| self.mwc(state, player, &Probabilities::single(p)));
+
+        (2.0 * self.mwc(state, player, probs) - (win + lose)) / (win - lose)
+    }
+
+    fn at(&self, [away, opponent_away]: [i64; 2], post_crawford: bool) -> f64 {
+        if away <= 0 {
+            return 1.0;
+        }
+        if opponent_away <= 0 {
+            return 0.0;
+        }
+
+        let last = self.pre_crawford.len() - 1;
+        let index = |away: i64| (away as usize - 1).min(last);
+        let [i, j] = [index(away), index(opponent_away)];
+
+        match (i, j) {
+            (0, j) if post_crawford => self.post_crawford[j],
+            (i, 0) if post_crawford => 1.0 - self.post_crawford[i],
+            (i, j) => self.pre_crawford[i][j],
+        }
+    }
+}
+
+// How many points `player` and their opponent need.
+fn away(state: &MatchState, player: bool) -> [i64; 2] {
+    assert!(state.match_length > 0, "Money play has no match equity");
+
+    // Light is index 0.
+    let score = |p: bool| state.score[(!p) as usize] as i64;
+    let length = state.match_length as i64;
+
+    [length - score(player), length - score(!player)]
+}
+
+/// Writes the text form read by [`str::parse`].
+impl Display for MatchEquityTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let row = |values: &[f64]| {
+            let values: Vec<_> =
+                values.iter().map(|v| format!("{:.4}", 100.0 * v)).collect();
+            values.join(" ")
+        };
+
+        writeln!(f, "name {}", self.name)?;
+        writeln!(f, "pre-crawford")?;
+        for values in &self.pre_crawford {
+            writeln!(f, "{}", row(values))?;
+        }
+        writeln!(f, "post-crawford")?;
+        writeln!(f, "{}", row(&self.post_crawford))
+    }
+}
+
+impl FromStr for MatchEquityTable {
+    type Err = MetError;
+
+    fn from_str(text: &str) -> Result<Self, MetError> {
+        let mut name = String::new();
+        // Rows with the lines they are on.
+        let mut pre_crawford: Vec<(usize, Vec<f64>)> = Vec::new();
+        let mut post_crawford = None;
+
+        let mut section = None;
+        let mut last_line = 0;
+
+        for (n, line) in text.lines().enumerate() {
+            let n = n + 1;
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            last_line = n;
+
+            if let Some(rest) = line.strip_prefix("name ") {
+                name = rest.trim().to_owned();
+                continue;
+            }
+            match line {
+                "pre-crawford" | "post-crawford" => {
+                    section = Some(line);
+                    continue;
+                }
+                _ => {}
+            }
+
+            let values = line
+                .split_whitespace()
+                .map(|value| {
+                    let percent: f64 = value.parse().map_err(|_| {
+                        MetError::new(n, MetErrorKind::Syntax(value.into()))
+                    })?;
+                    if !(0.0..=100.0).contains(&percent) {
+                        return Err(MetError::new(
+                            n,
+                            MetErrorKind::Range(percent),
+                        ));
+                    }
+                    Ok(percent / 100.0)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            match section {
+                Some("pre-crawford") => pre_crawford.push((n, values)),
+                Some(_) if post_crawford.is_none() => {
+                    post_crawford = Some((n, values))
+                }
+                _ => {
+                    return Err(MetError::new(
+                        n,
+                        MetErrorKind::Syntax(line.into()),
+                    ))
+                }
+            }
+        }
+
+        let size = pre_crawford.len();
+        let missing = MetError::new(last_line, MetErrorKind::Missing);
+        if size == 0 {
+            return Err(missing);
+        }
+        let post_crawford = post_crawford.ok_or(missing)?;
+
+        for (n, row) in pre_crawford.iter().chain([&post_crawford]) {
+            if row.len() != size {
+                return Err(MetError::new(
+                    *n,
+                    MetErrorKind::RowLength {
+                        expected: size,
+                        found: row.len(),
+                    },
+                ));
+            }
+        }
+
+        Ok(Self {
+            name,
+            pre_crawford: pre_crawford
+                .into_iter()
+                .map(|(_, row)| row)
+                .collect(),
+            post_crawford: post_crawford.1,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetError {
+    /// Numbered from 1.
+    pub line: usize,
+    pub kind: MetErrorKind,
+}
+
+impl MetError {
+    fn new(line: usize, kind: MetErrorKind) -> Self {
+        Self { line, kind }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetErrorKind {
+    /// A line or value that could not be parsed.
+    Syntax(String),
+    /// A percentage outside 0 to 100.
+    Range(f64),
+    /// A row with a different number of entries than the table has rows.
+    RowLength { expected: usize, found: usize },
+    /// The table has no pre-Crawford or no post-Crawford rows.
+    Missing,
+}
+
+impl Display for MetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+
+        match &self.kind {
+            MetErrorKind::Syntax(text) => write!(f, "Could not parse {text:?}"),
+            MetErrorKind::Range(value) => {
+                write!(f, "{value} is not a percentage")
+            }
+            MetErrorKind::RowLength { expected, found } => {
+                write!(f, "Expected {expected} entries in a row, found {found}")
+            }
+            MetErrorKind::Missing => {
+                write!(f, "Expected pre-crawford and post-crawford rows")
+            }
+        }
+    }
+}
+
+impl Error for MetError {}
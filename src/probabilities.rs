@@ -0,0 +1,146 @@
+use std::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, Mul},
+};
+
+use crate::backgammon::GameResult;
+
+/// Cubeless chances of each way a game can end, for one player, in the form
+/// backgammon programs report them. Each chance includes the ones after it
+/// on the same side: `win` counts gammons and `win_gammon` counts
+/// backgammons.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Probabilities {
+    pub win: f64,
+    pub win_gammon: f64,
+    pub win_backgammon: f64,
+    pub lose_gammon: f64,
+    pub lose_backgammon: f64,
+}
+
+impl Probabilities {
+    /// Chances without gammons.
+    pub fn single(win: f64) -> Self {
+        Self {
+            win,
+            win_gammon: 0.0,
+            win_backgammon: 0.0,
+            lose_gammon: 0.0,
+            lose_backgammon: 0.0,
+        }
+    }
+
+    /// The certain chances of `player` in a finished game.
+    pub fn from_result(result: GameResult, player: bool) -> Self {
+        let points = result.points();
+        let [gammon, backgammon] = [points >= 2, points >= 3].map(f64::from);
+
+        let won = Self {
+            win: 1.0,
+            win_gammon: gammon,
+            win_backgammon: backgammon,
+            ..Self::default()
+        };
+        if result.winner == player {
+            won
+        } else {
+            won.flip()
+        }
+    }
+
+    /// The same chances from the opponent's side.
+    pub fn flip(&self) -> Self {
+        Self {
+            win: 1.0 - self.win,
+            win_gammon: self.lose_gammon,
+            win_backgammon: self.lose_backgammon,
+            lose_gammon: self.win_gammon,
+            lose_backgammon: self.win_backgammon,
+        }
+    }
+
+    pub fn lose(&self) -> f64 {
+        1.0 - self.win
+    }
+
+    /// The expected points won per point of cube when the cube is never
+    /// turned.
+    pub fn cubeless_equity(&self) -> f64 {
+        self.outcomes()
+            .iter()
+            .map(|&(p, points)| p * points as f64)
+            .sum()
+    }
+
+    /// The chance of each result on its own with the points it is worth,
+    /// from a single win to a backgammon loss.
+    pub fn outcomes(&self) -> [(f64, i32); 6] {
+        [
+            (self.win - self.win_gammon, 1),
+            (self.win_gammon - self.win_backgammon, 2),
+            (self.win_backgammon, 3),
+            (self.lose() - self.lose_gammon, -1),
+            (self.lose_gammon - self.lose_backgammon, -2),
+            (self.lose_backgammon, -3),
+        ]
+    }
+}
+
+// Addition and scaling average chances over rolls.
+impl Add for Probabilities {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            win: self.win + other.win,
+            win_gammon: self.win_gammon + other.win_gammon,
+            win_backgammon: self.win_backgammon + other.win_backgammon,
+            lose_gammon: self.lose_gammon + other.lose_gammon,
+            lose_backgammon: self.lose_backgammon + other.lose_backgammon,
+        }
+    }
+}
+
+impl Mul<f64> for Probabilities {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self {
+            win: self.win * factor,
+            win_gammon: self.win_gammon * factor,
+            win_backgammon: self.win_backgammon * factor,
+            lose_gammon: self.lose_gammon * factor,
+            lose_backgammon: self.lose_backgammon * factor,
+        }
+    }
+}
+
+impl Sum for Probabilities {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// The six figures GNU Backgammon shows, as percentages: win, gammon and
+/// backgammon, then the same for losing, followed by the cubeless equity.
+impl Display for Probabilities {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [w, wg, wbg, l, lg, lbg] = [
+            self.win,
+            self.win_gammon,
+            self.win_backgammon,
+            self.lose(),
+            self.lose_gammon,
+            self.lose_backgammon,
+        ]
+        .map(|p| 100.0 * p);
+
+        write!(
+            f,
+            "{w:.1} {wg:.1} {wbg:.1} - {l:.1} {lg:.1} {lbg:.1} ({:+.3})",
+            self.cubeless_equity()
+        )
+    }
+}
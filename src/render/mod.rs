@@ -0,0 +1,307 @@
+//! Text drawings of positions for any [`Backgammon`] implementation.
+//!
+//! ```
+//! use backgammon_sim::{
+//!     render::{Renderer, Theme},
+//!     GameState,
+//! };
+//!
+//! let state = GameState::new_with_default_setup();
+//! let board = Renderer::new(Theme::Ascii, false).render(&state);
+//! assert!(board.is_ascii());
+//! ```
+
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{backgammon::Backgammon, i8_based::notation::point_index};
+
+pub use svg::SvgRenderer;
+
+mod svg;
+
+const COLOURS: [&str; 2] = ["\x1b[33m", "\x1b[36m"];
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Theme {
+    /// Box drawing characters with ANSI colours.
+    #[default]
+    Ansi,
+    /// The same board without colours, with hollow checkers for Light.
+    Unicode,
+    /// Plain ASCII in the style of GNU Backgammon's `show board`.
+    Ascii,
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "ansi" => Ok(Self::Ansi),
+            "unicode" => Ok(Self::Unicode),
+            "ascii" => Ok(Self::Ascii),
+            _ => Err(format!("Unknown theme {s:?}")),
+        }
+    }
+}
+
+/// Draws positions in a [`Theme`] from one player's side. The default is how
+/// the engines' `Display` impls draw: ANSI colours from Light's side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Renderer {
+    pub theme: Theme,
+    /// The player whose point numbers are shown, with their home board on
+    /// the right.
+    pub perspective: bool,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new(Theme::Ansi, true)
+    }
+}
+
+impl Renderer {
+    pub fn new(theme: Theme, perspective: bool) -> Self {
+        Self { theme, perspective }
+    }
+
+    /// `state` drawn by this renderer when formatted.
+    pub fn board<'a, B: Backgammon>(&self, state: &'a B) -> Board<'a, B> {
+        Board {
+            renderer: *self,
+            state,
+        }
+    }
+
+    pub fn render<B: Backgammon>(&self, state: &B) -> String {
+        self.board(state).to_string()
+    }
+
+    pub(crate) fn write<B: Backgammon>(
+        &self,
+        f: &mut Formatter<'_>,
+        state: &B,
+    ) -> fmt::Result {
+        match self.theme {
+            Theme::Ansi => self.write_boxed(f, state, true),
+            Theme::Unicode => self.write_boxed(f, state, false),
+            Theme::Ascii => self.write_ascii(f, state),
+        }
+    }
+
+    // Checkers of `point` numbered from the perspective's side, positive for
+    // the perspective.
+    fn point<B: Backgammon>(&self, state: &B, point: u8) -> i8 {
+        let n = state
+            .checkers(point_index(self.perspective, point).unwrap() as usize);
+        if self.perspective {
+            n
+        } else {
+            -n
+        }
+    }
+
+    // Points 24 to 13 run down the left and 1 to 12 down the right, so the
+    // perspective's home board is at the top right.
+    fn write_boxed<B: Backgammon>(
+        &self,
+        f: &mut Formatter<'_>,
+        state: &B,
+        colour: bool,
+    ) -> fmt::Result {
+        // [opponent, perspective], as drawn from left to right.
+        let sides = [!self.perspective, self.perspective];
+        // Light is index 0.
+        let index = |player: bool| (!player) as usize;
+
+        let paint = |player: bool, text: &str| {
+            if colour && !text.is_empty() {
+                format!("{}{text}{RESET}", COLOURS[index(player)])
+            } else {
+                text.to_owned()
+            }
+        };
+        let glyph = |player: bool| match (colour, player) {
+            (false, true) => "○",
+            _ => "●",
+        };
+        let name = |player: bool| if player { "LIGHT" } else { "DARK" };
+        let checkers = |player: bool, n: u8| {
+            paint(player, &glyph(player).repeat(n as usize))
+        };
+        let [left, right] = sides;
+        let divider = "  ╠═════════════════╬═════════════════╣";
+
+        writeln!(f, "  ╔═════════════════╦═════════════════╗")?;
+        let pips = state.pip_counts();
+        writeln!(
+            f,
+            "  ║REMAINING:  {}  ║  {}            ║",
+            paint(left, &format!("{:3}", pips[index(left)])),
+            paint(right, &format!("{:<3}", pips[index(right)])),
+        )?;
+
+        // Rows with counts of checkers drawn against the outer edges.
+        let counted = |f: &mut Formatter<'_>, counts: [u8; 2]| {
+            let [l, r] = counts;
+            writeln!(
+                f,
+                "  ║{}{}  ║  {}{}║",
+                checkers(left, l),
+                " ".repeat(15 - l as usize),
+                " ".repeat(15 - r as usize),
+                checkers(right, r),
+            )
+        };
+
+        writeln!(f, "{divider}")?;
+        writeln!(
+            f,
+            "  ║{}║{}║",
+            paint(left, &format!("{:<17}", format!("HOME {}", name(left)))),
+            paint(right, &format!("{:>17}", format!("HOME {}", name(right)))),
+        )?;
+        let off = state.borne_off();
+        counted(f, sides.map(|p| off[index(p)]))?;
+
+        writeln!(f, "{divider}")?;
+        for row in 0..12u8 {
+            let dashes = |player: bool, n: u8| {
+                paint(player, &"-".repeat(15 - n as usize))
+            };
+            // Alternate the colour of the empty space between rows.
+            let stripe = [row % 2 == 1, row % 2 == 0];
+
+            let [l, r] = [24 - row, row + 1].map(|p| self.point(state, p));
+            let owner = |n: i8| {
+                if n > 0 {
+                    self.perspective
+                } else {
+                    !self.perspective
+                }
+            };
+
+            write!(f, "{:2}║", 24 - row)?;
+            write!(
+                f,
+                "{}{}",
+                checkers(owner(l), l.unsigned_abs()),
+                dashes(stripe[0], l.unsigned_abs())
+            )?;
+            write!(f, "  ║  ")?;
+            write!(
+                f,
+                "{}{}",
+                dashes(stripe[1], r.unsigned_abs()),
+                checkers(owner(r), r.unsigned_abs())
+            )?;
+            writeln!(f, "║{}", row + 1)?;
+
+            if row == 5 {
+                writeln!(f, "{divider}")?;
+            }
+        }
+
+        writeln!(f, "{divider}")?;
+        writeln!(
+            f,
+            "  ║{}║{}║",
+            paint(left, &format!("{:<17}", "CAPTURED")),
+            paint(right, &format!("{:>17}", "CAPTURED")),
+        )?;
+        let bar = state.bar();
+        counted(f, sides.map(|p| bar[index(p)]))?;
+
+        write!(f, "  ╚═════════════════╩═════════════════╝")
+    }
+
+    // The perspective plays X from the top left round to the bottom right.
+    fn write_ascii<B: Backgammon>(
+        &self,
+        f: &mut Formatter<'_>,
+        state: &B,
+    ) -> fmt::Result {
+        const ROWS: usize = 5;
+
+        let name = |player: bool| if player { "Light" } else { "Dark" };
+        let index = |player: bool| (!player) as usize;
+        let summary = |player: bool| {
+            let i = index(player);
+            format!(
+                "     pips {}, bar {}, off {}",
+                state.pip_counts()[i],
+                state.bar()[i],
+                state.borne_off()[i]
+            )
+        };
+
+        // The cell of `point` in the `depth`th row from its edge.
+        let cell = |point: u8, depth: usize| {
+            let n = self.point(state, point);
+            let count = n.unsigned_abs() as usize;
+            let mark = if n > 0 { 'X' } else { 'O' };
+
+            match depth {
+                _ if depth >= count => "   ".to_owned(),
+                // Stacks too tall to draw end with their size.
+                d if d == ROWS - 1 && count > ROWS => format!("{count:2} "),
+                _ => format!(" {mark} "),
+            }
+        };
+        let row = |points: [u8; 12], depth: usize| {
+            let cells: Vec<_> =
+                points.iter().map(|&p| cell(p, depth)).collect();
+            format!(" |{}|   |{}|", cells[..6].concat(), cells[6..].concat())
+        };
+
+        let top = std::array::from_fn(|i| 13 + i as u8);
+        let bottom = std::array::from_fn(|i| 12 - i as u8);
+
+        writeln!(
+            f,
+            " +13-14-15-16-17-18------19-20-21-22-23-24-+     O: {}",
+            name(!self.perspective)
+        )?;
+        for depth in 0..ROWS {
+            let side = if depth == 0 {
+                summary(!self.perspective)
+            } else {
+                String::new()
+            };
+            writeln!(f, "{}{side}", row(top, depth))?;
+        }
+
+        writeln!(f, " |                  |BAR|                  |")?;
+
+        for depth in (0..ROWS).rev() {
+            let side = if depth == 0 {
+                summary(self.perspective)
+            } else {
+                String::new()
+            };
+            writeln!(f, "{}{side}", row(bottom, depth))?;
+        }
+        write!(
+            f,
+            " +12-11-10--9--8--7-------6--5--4--3--2--1-+     X: {}",
+            name(self.perspective)
+        )
+    }
+}
+
+/// A position with the [`Renderer`] to draw it, from [`Renderer::board`].
+pub struct Board<'a, B> {
+    renderer: Renderer,
+    state: &'a B,
+}
+
+impl<B: Backgammon> Display for Board<'_, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.renderer.write(f, self.state)
+    }
+}
@@ -0,0 +1,292 @@
+use std::fmt::Write;
+
+use crate::{
+    backgammon::Backgammon, i8_based::notation::point_index,
+    match_state::MatchState,
+};
+
+// Layout, in SVG user units.
+const POINT: u32 = 40;
+const RADIUS: u32 = 18;
+const HALF: u32 = 6 * POINT;
+const BAR: u32 = 40;
+const LEFT: u32 = 60;
+const TOP: u32 = 55;
+const INNER_HEIGHT: u32 = 440;
+const BOTTOM: u32 = TOP + INNER_HEIGHT;
+const MIDDLE: u32 = TOP + INNER_HEIGHT / 2;
+const TRAY: u32 = LEFT + 2 * HALF + BAR + 20;
+const WIDTH: u32 = TRAY + 60;
+const HEIGHT: u32 = BOTTOM + 60;
+
+// Checkers drawn on a point before the stack shows its size instead.
+const STACK: u32 = 5;
+
+const FELT: &str = "#2f6b4f";
+const FRAME: &str = "#6b4226";
+const POINTS: [&str; 2] = ["#d9b98c", "#9c3d2e"];
+// Fill and outline, indexed [light, dark].
+const CHECKERS: [[&str; 2]; 2] = [["#f5ecd7", "#8a7b5c"], ["#2b2b2b", "#000"]];
+
+/// Draws positions as SVG diagrams, with the same information as the
+/// terminal [`Renderer`](super::Renderer) plus the dice and cube.
+///
+/// The perspective's home board is at the bottom right, and points are
+/// numbered from their side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SvgRenderer {
+    pub perspective: bool,
+    /// The player on roll and their dice.
+    pub roll: Option<(bool, [u8; 2])>,
+    /// The cube value and owner, with `None` as the owner while it is
+    /// centred. No cube is drawn if this is `None`.
+    pub cube: Option<(u32, Option<bool>)>,
+}
+
+impl SvgRenderer {
+    /// Draws just the checkers.
+    pub fn new(perspective: bool) -> Self {
+        Self {
+            perspective,
+            roll: None,
+            cube: None,
+        }
+    }
+
+    /// Draws `state` from the side of the player on roll, with their dice
+    /// and the cube.
+    pub fn for_match(state: &MatchState) -> Self {
+        Self {
+            perspective: state.on_roll,
+            roll: state.dice.map(|dice| (state.on_roll, dice)),
+            cube: Some((state.cube_value, state.cube_owner)),
+        }
+    }
+
+    pub fn render<B: Backgammon>(&self, state: &B) -> String {
+        let mut svg = String::new();
+        self.write(&mut svg, state).unwrap();
+        svg
+    }
+
+    fn write<B: Backgammon>(
+        &self,
+        svg: &mut String,
+        state: &B,
+    ) -> std::fmt::Result {
+        // Light is index 0.
+        let index = |player: bool| (!player) as usize;
+        let [opponent, own] = [!self.perspective, self.perspective];
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" text-anchor="middle">"#
+        )?;
+        writeln!(
+            svg,
+            r##"<rect width="{WIDTH}" height="{HEIGHT}" fill="#fff"/>"##
+        )?;
+        writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{FRAME}"/>"#,
+            LEFT - 10,
+            TOP - 10,
+            2 * HALF + BAR + 20,
+            INNER_HEIGHT + 20
+        )?;
+        for x in [LEFT, LEFT + HALF + BAR] {
+            writeln!(
+                svg,
+                r#"<rect x="{x}" y="{TOP}" width="{HALF}" height="{INNER_HEIGHT}" fill="{FELT}"/>"#
+            )?;
+        }
+        writeln!(
+            svg,
+            r#"<rect x="{TRAY}" y="{TOP}" width="40" height="{INNER_HEIGHT}" fill="{FRAME}"/>"#
+        )?;
+
+        // Points 13 to 24 run along the top from the left and 12 to 1 along
+        // the bottom.
+        for column in 0..12 {
+            let x = LEFT + column * POINT + if column < 6 { 0 } else { BAR };
+            let centre = x + POINT / 2;
+
+            for (point, top) in [(13 + column, true), (12 - column, false)] {
+                let (edge, tip, label_y) = if top {
+                    (TOP, TOP + 180, TOP - 15)
+                } else {
+                    (BOTTOM, BOTTOM - 180, BOTTOM + 27)
+                };
+                let colour = POINTS[(point % 2) as usize];
+
+                writeln!(
+                    svg,
+                    r#"<polygon points="{x},{edge} {},{edge} {centre},{tip}" fill="{colour}"/>"#,
+                    x + POINT
+                )?;
+                writeln!(
+                    svg,
+                    r#"<text x="{centre}" y="{label_y}" font-size="14">{point}</text>"#
+                )?;
+
+                let index = point_index(self.perspective, point as u8).unwrap();
+                let n = state.checkers(index as usize);
+                let owner = n > 0;
+                let count = n.unsigned_abs() as u32;
+
+                for k in 0..count.min(STACK) {
+                    let offset = RADIUS + k * 2 * RADIUS;
+                    let y = if top { TOP + offset } else { BOTTOM - offset };
+                    let label =
+                        (k == STACK - 1 && count > STACK).then_some(count);
+                    checker(svg, centre, y, owner, label)?;
+                }
+            }
+        }
+
+        // Checkers on the bar stack away from the middle, the opponent's
+        // above it.
+        let bar = state.bar();
+        let bar_x = LEFT + HALF + BAR / 2;
+        for (player, up) in [(opponent, true), (own, false)] {
+            let count = bar[index(player)] as u32;
+            for k in 0..count.min(3) {
+                let offset = 2 * RADIUS + k * 2 * RADIUS;
+                let y = if up { MIDDLE - offset } else { MIDDLE + offset };
+                let label = (k == 2 && count > 3).then_some(count);
+                checker(svg, bar_x, y, player, label)?;
+            }
+        }
+
+        // Borne off checkers are drawn side on in the tray.
+        let off = state.borne_off();
+        for (player, top) in [(opponent, true), (own, false)] {
+            let [fill, stroke] = CHECKERS[index(player)];
+            for k in 0..off[index(player)] as u32 {
+                let y = if top {
+                    TOP + 4 + k * 12
+                } else {
+                    BOTTOM - 14 - k * 12
+                };
+                writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{y}" width="32" height="10" rx="2" fill="{fill}" stroke="{stroke}"/>"#,
+                    TRAY + 4
+                )?;
+            }
+        }
+
+        let pips = state.pip_counts();
+        for (player, y) in [(opponent, 20), (own, HEIGHT - 10)] {
+            let name = if player { "Light" } else { "Dark" };
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{y}" font-size="16">{name}: {} pips, {} off</text>"#,
+                LEFT + HALF + BAR / 2,
+                pips[index(player)],
+                off[index(player)]
+            )?;
+        }
+
+        if let Some((player, dice)) = self.roll {
+            // In the right half for the perspective.
+            let centre = if player == self.perspective {
+                LEFT + HALF + BAR + HALF / 2
+            } else {
+                LEFT + HALF / 2
+            };
+            for (i, &value) in dice.iter().enumerate() {
+                let x = centre - 40 + i as u32 * 48;
+                die(svg, x, MIDDLE - 16, player, value)?;
+            }
+        }
+
+        if let Some((value, owner)) = self.cube {
+            let y = match owner {
+                None => MIDDLE - 18,
+                Some(owner) if owner == self.perspective => BOTTOM - 36,
+                Some(_) => TOP,
+            };
+            // A centred cube shows 64, as on a real board.
+            let shown = if owner.is_none() && value == 1 {
+                64
+            } else {
+                value
+            };
+            writeln!(
+                svg,
+                r##"<rect x="8" y="{y}" width="36" height="36" rx="4" fill="#fff" stroke="#000"/>"##
+            )?;
+            writeln!(
+                svg,
+                r#"<text x="26" y="{}" font-size="16" font-weight="bold">{shown}</text>"#,
+                y + 24
+            )?;
+        }
+
+        writeln!(svg, "</svg>")
+    }
+}
+
+fn checker(
+    svg: &mut String,
+    x: u32,
+    y: u32,
+    player: bool,
+    label: Option<u32>,
+) -> std::fmt::Result {
+    let [fill, stroke] = CHECKERS[(!player) as usize];
+    writeln!(
+        svg,
+        r#"<circle cx="{x}" cy="{y}" r="{}" fill="{fill}" stroke="{stroke}" stroke-width="2"/>"#,
+        RADIUS - 1
+    )?;
+
+    if let Some(count) = label {
+        // Drawn in the other player's colour to stand out.
+        let [text, _] = CHECKERS[player as usize];
+        writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" font-size="16" font-weight="bold" fill="{text}">{count}</text>"#,
+            y + 6
+        )?;
+    }
+
+    Ok(())
+}
+
+fn die(
+    svg: &mut String,
+    x: u32,
+    y: u32,
+    player: bool,
+    value: u8,
+) -> std::fmt::Result {
+    let [fill, stroke] = CHECKERS[(!player) as usize];
+    let [pip, _] = CHECKERS[player as usize];
+
+    writeln!(
+        svg,
+        r#"<rect x="{x}" y="{y}" width="32" height="32" rx="5" fill="{fill}" stroke="{stroke}" stroke-width="2"/>"#
+    )?;
+
+    // Pips on a 3 by 3 grid, numbered row by row.
+    let spots: &[u32] = match value {
+        1 => &[4],
+        2 => &[0, 8],
+        3 => &[0, 4, 8],
+        4 => &[0, 2, 6, 8],
+        5 => &[0, 2, 4, 6, 8],
+        _ => &[0, 2, 3, 5, 6, 8],
+    };
+    for &spot in spots {
+        writeln!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="3" fill="{pip}"/>"#,
+            x + 7 + spot % 3 * 9,
+            y + 7 + spot / 3 * 9
+        )?;
+    }
+
+    Ok(())
+}
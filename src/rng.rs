@@ -0,0 +1,63 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Small, fast and seedable pseudo random number generator (SplitMix64).
+///
+/// The sequence produced for a given seed is fixed, so anything driven by it
+/// can be replayed exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeded from the system clock, for when reproducibility does not
+    /// matter.
+    pub fn from_time() -> Self {
+        let t = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self::new(t)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Empty range");
+
+        // Rejection sampling keeps every value equally likely.
+        let zone = u64::MAX - u64::MAX % n as u64;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return (x % n as u64) as usize;
+            }
+        }
+    }
+
+    /// Uniform in `0.0..1.0`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn roll_die(&mut self) -> u8 {
+        self.below(6) as u8 + 1
+    }
+
+    pub fn roll_dice(&mut self) -> [u8; 2] {
+        [self.roll_die(), self.roll_die()]
+    }
+}
@@ -0,0 +1,39 @@
+//! Fixtures shared by the integration tests. Not every test uses all of
+//! them.
+#![allow(dead_code)]
+
+use backgammon_sim::{
+    dice::SeededDice,
+    game::{play_game, RandomPlayer},
+    rng::Rng,
+    Backgammon, GameState,
+};
+
+// A position from (index, checkers) pairs, positive for Light, with the
+// checkers on the bar and borne off as [light, dark].
+pub fn position<B: Backgammon>(
+    points: &[(usize, i8)],
+    bar: [u8; 2],
+    borne_off: [u8; 2],
+) -> B {
+    let mut tiles = [0; 24];
+    for &(i, n) in points {
+        tiles[i] = n;
+    }
+    B::from_parts(tiles, bar, borne_off)
+}
+
+// The positions after each turn of `games` random games, with the player to
+// move next. Different seeds give different games.
+pub fn sample_positions(seed: u64, games: usize) -> Vec<(GameState, bool)> {
+    let mut dice = SeededDice::new(seed);
+    let mut light = RandomPlayer::new(Rng::new(seed + 1));
+    let mut dark = RandomPlayer::new(Rng::new(seed + 2));
+
+    let mut positions = Vec::new();
+    for _ in 0..games {
+        let record = play_game([&mut light, &mut dark], &mut dice);
+        positions.extend(record.turns.iter().map(|t| (t.state, !t.turn)));
+    }
+    positions
+}
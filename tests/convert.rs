@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use backgammon_sim::{
+    differential::random_position,
+    enum_based::{self, Tile},
+    rng::Rng,
+    Backgammon, GameState, MoveBuffer, PositionError,
+};
+
+#[test]
+fn positions_round_trip() {
+    let mut rng = Rng::new(8);
+
+    let positions = (0..100).map(|_| random_position(&mut rng));
+    for state in positions.chain([GameState::new_with_default_setup()]) {
+        let converted = enum_based::GameState::try_from(state).unwrap();
+
+        for point in 0..24 {
+            assert_eq!(converted.checkers(point), state.checkers(point));
+        }
+        assert_eq!(converted.bar(), state.bar());
+        assert_eq!(converted.borne_off(), state.borne_off());
+
+        assert_eq!(GameState::try_from(&converted), Ok(state));
+    }
+}
+
+#[test]
+fn converted_positions_play_the_same() {
+    let state = GameState::new_with_default_setup();
+    let converted = enum_based::GameState::try_from(state).unwrap();
+
+    let mut moves = MoveBuffer::new();
+    moves.generate(true, state, [6, 4]);
+
+    let expected: HashSet<_> = moves.state_iterator().collect();
+    let plays: HashSet<_> = converted
+        .legal_plays(true, [6, 4])
+        .iter()
+        .map(|s| GameState::try_from(s).unwrap())
+        .collect();
+    assert_eq!(plays, expected);
+}
+
+#[test]
+fn rejects_wrong_checker_counts() {
+    let mut tiles = [0; 24];
+    tiles[0] = 15;
+    tiles[23] = -14;
+    let state = GameState::from_parts(tiles, [0, 0], [0, 0]);
+
+    let err = enum_based::GameState::try_from(state).unwrap_err();
+    assert_eq!(
+        err,
+        PositionError::CheckerCount {
+            player: false,
+            count: 14
+        }
+    );
+    assert_eq!(err.to_string(), "Dark has 14 checkers instead of 15");
+
+    let state = enum_based::GameState::from_parts(tiles, [1, 1], [0, 0]);
+    assert_eq!(
+        GameState::try_from(&state),
+        Err(PositionError::CheckerCount {
+            player: true,
+            count: 16
+        })
+    );
+
+    assert_eq!(
+        GameState::empty().validate().map_err(|e| e.to_string()),
+        Err("Light has 0 checkers instead of 15".to_owned())
+    );
+}
+
+#[test]
+fn tiles_convert_to_signed_counts() {
+    for n in -15..=15 {
+        assert_eq!(i8::from(Tile::from(n)), n);
+    }
+
+    assert_eq!(Tile::from(0), Tile::Empty);
+    assert_eq!(Tile::from(3), Tile::Light(3));
+    assert_eq!(Tile::from(-2), Tile::Dark(2));
+}
@@ -0,0 +1,248 @@
+use backgammon_sim::{
+    cube::{Chances, Cube, CubeResponse, CubefulEquity},
+    dice::{DiceSource, ScriptedDice, SeededDice},
+    game::{
+        play_cubeful_game, Action, CubeRules, EvaluatorPlayer, PipCountPlayer,
+        Player,
+    },
+    Backgammon, Evaluator, GameState, MoveBuffer,
+};
+
+// Doubles whenever it may and answers doubles with a fixed response.
+struct CubeHappy(CubeResponse);
+
+impl Player for CubeHappy {
+    fn choose_move(&mut self, moves: &MoveBuffer) -> GameState {
+        PipCountPlayer.choose_move(moves)
+    }
+
+    fn offer_double(&mut self, _: GameState, _: bool, _: Cube) -> bool {
+        true
+    }
+
+    fn answer_double(
+        &mut self,
+        _: GameState,
+        _: bool,
+        _: Cube,
+        _: bool,
+    ) -> CubeResponse {
+        self.0
+    }
+}
+
+// Light to roll, far ahead in a race with everyone in their home boards.
+fn race() -> GameState {
+    let mut checkers = [0; 24];
+    checkers[23] = 5;
+    checkers[22] = 5;
+    checkers[0] = -5;
+    checkers[1] = -5;
+    checkers[2] = -5;
+    GameState::from_parts(checkers, [0, 0], [5, 0])
+}
+
+#[test]
+fn cube_ownership() {
+    let cube = Cube::new();
+    assert!(cube.is_centred());
+    assert!(cube.may_double(true) && cube.may_double(false));
+
+    let cube = cube.taken(false);
+    assert_eq!(
+        cube,
+        Cube {
+            value: 2,
+            owner: Some(false)
+        }
+    );
+    assert!(!cube.may_double(true));
+
+    assert_eq!(
+        cube.beavered(true),
+        Cube {
+            value: 8,
+            owner: Some(true)
+        }
+    );
+}
+
+#[test]
+fn live_cube_points() {
+    let chances = Chances::single(0.5);
+    assert!((chances.take_point() - 0.2).abs() < 1e-9);
+    assert!((chances.cash_point() - 0.8).abs() < 1e-9);
+
+    // A fully live cube is cashed at the cash point.
+    let at_cash = Chances::single(0.8);
+    assert!((at_cash.cubeful_equity(None, 1.0) - 1.0).abs() < 1e-9);
+    assert!((at_cash.cubeful_equity(Some(true), 1.0) - 1.0).abs() < 1e-9);
+    // A dead cube is cubeless.
+    assert!((at_cash.cubeful_equity(None, 0.0) - 0.6).abs() < 1e-9);
+}
+
+#[test]
+fn cube_decisions_follow_the_chances() {
+    let decision = |p| CubefulEquity::new(&Chances::single(p), None);
+
+    assert!(!decision(0.55).should_double());
+    assert_eq!(decision(0.55).response(true), CubeResponse::Beaver);
+
+    assert!(decision(0.75).should_double());
+    assert_eq!(decision(0.75).response(true), CubeResponse::Take);
+
+    assert!(decision(0.9).should_double());
+    assert_eq!(decision(0.9).response(false), CubeResponse::Drop);
+    assert_eq!(decision(0.9).equity(), 1.0);
+}
+
+#[test]
+fn gammons_count_beyond_the_cube_points() {
+    let owners = [None, Some(true), Some(false)];
+
+    let sure_gammon_loss = Chances {
+        win: 0.0,
+        win_value: 1.0,
+        loss_value: 2.0,
+    };
+    let sure_gammon_win = Chances {
+        win: 1.0,
+        win_value: 2.0,
+        loss_value: 1.0,
+    };
+    for owner in owners {
+        let equity = sure_gammon_loss.cubeful_equity(owner, 1.0);
+        assert!((equity + 2.0).abs() < 1e-9, "{owner:?}: {equity}");
+        let equity = sure_gammon_win.cubeful_equity(owner, 1.0);
+        assert!((equity - 2.0).abs() < 1e-9, "{owner:?}: {equity}");
+    }
+
+    // Past the cash point with plenty of gammons, playing on is worth more
+    // than the point a double would cash.
+    let too_good = CubefulEquity::new(
+        &Chances {
+            win: 0.78,
+            win_value: 1.5,
+            loss_value: 1.0,
+        },
+        None,
+    );
+    assert!(too_good.no_double > 1.0);
+    assert!(!too_good.should_double());
+    assert_eq!(too_good.response(false), CubeResponse::Drop);
+}
+
+#[test]
+fn evaluator_doubles_out_a_won_race() {
+    let mut evaluator = Evaluator::new();
+
+    let eval = evaluator.get_cubeful_eval(race(), true, Cube::new(), 1);
+    assert!(eval.should_double());
+    assert_eq!(eval.response(false), CubeResponse::Drop);
+
+    let eval = evaluator.get_cubeful_eval(race(), false, Cube::new(), 1);
+    assert!(!eval.should_double());
+}
+
+#[test]
+fn dropped_doubles_end_the_game() {
+    let mut light = CubeHappy(CubeResponse::Drop);
+    let mut dark = CubeHappy(CubeResponse::Drop);
+    let mut dice = ScriptedDice::new([[3, 1], [6, 5]]);
+
+    let record = play_cubeful_game(
+        [&mut light, &mut dark],
+        &mut dice,
+        CubeRules::default(),
+    );
+
+    // Light opens and Dark doubles before their first roll.
+    assert_eq!(record.actions.len(), 3);
+    assert_eq!(
+        record.actions[1..],
+        [
+            Action::Double {
+                player: false,
+                value: 2
+            },
+            Action::Drop(true)
+        ]
+    );
+    assert!(!record.winner);
+    assert_eq!(record.result, None);
+    assert_eq!(record.points(), 1);
+    // Dark's roll was never drawn.
+    assert_eq!(dice.roll(), [6, 5]);
+}
+
+#[test]
+fn beavers_need_the_rules() {
+    let play = |rules| {
+        let mut light = CubeHappy(CubeResponse::Beaver);
+        let mut dark = CubeHappy(CubeResponse::Take);
+        play_cubeful_game(
+            [&mut light, &mut dark],
+            &mut SeededDice::new(3),
+            rules,
+        )
+    };
+
+    let record = play(CubeRules::money());
+    assert!(record.actions.contains(&Action::Beaver(true)));
+    assert_eq!(record.points() % 4, 0);
+
+    let record = play(CubeRules::default());
+    assert!(!record.actions.contains(&Action::Beaver(true)));
+    assert!(record.actions.contains(&Action::Take(true)));
+
+    let record = play(CubeRules {
+        doubling: [false, false],
+        ..CubeRules::money()
+    });
+    assert!(record
+        .actions
+        .iter()
+        .all(|action| matches!(action, Action::Roll(_))));
+}
+
+#[test]
+fn evaluator_players_keep_the_cube_legal() {
+    let mut light = EvaluatorPlayer::new(1);
+    let mut dark = EvaluatorPlayer::new(1);
+    let mut dice = SeededDice::new(11);
+
+    for _ in 0..10 {
+        let record = play_cubeful_game(
+            [&mut light, &mut dark],
+            &mut dice,
+            CubeRules::money(),
+        );
+
+        let mut cube = Cube::new();
+        for pair in record.actions.windows(2) {
+            if let [Action::Double { player, value }, answer] = pair {
+                assert!(cube.may_double(*player));
+                assert_eq!(*value, cube.value * 2);
+                assert_eq!(answer.player(), !player);
+                match answer {
+                    Action::Take(p) => cube = cube.taken(*p),
+                    Action::Beaver(p) => cube = cube.beavered(*p),
+                    Action::Drop(_) => {}
+                    _ => panic!("Double not answered"),
+                }
+            }
+        }
+        assert_eq!(record.cube, cube);
+
+        match record.result {
+            Some(result) => {
+                assert_eq!(record.final_state().result(), Some(result));
+                assert_eq!(record.points(), cube.value * result.points());
+            }
+            None => {
+                assert!(matches!(record.actions.last(), Some(Action::Drop(_))));
+                assert_eq!(record.points(), cube.value);
+            }
+        }
+    }
+}
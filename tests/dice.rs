@@ -0,0 +1,110 @@
+use std::{fs, io::Cursor};
+
+use backgammon_sim::{
+    dice::{DiceSource, FileDice, ScriptedDice, SeededDice},
+    game::{play_game, PipCountPlayer},
+};
+
+fn rolls(dice: &mut impl DiceSource, n: usize) -> Vec<[u8; 2]> {
+    (0..n).map(|_| dice.roll()).collect()
+}
+
+#[test]
+fn seeded_dice_are_reproducible() {
+    let a = rolls(&mut SeededDice::new(1234), 1000);
+    let b = rolls(&mut SeededDice::new(1234), 1000);
+    let c = rolls(&mut SeededDice::new(1235), 1000);
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert!(a.iter().flatten().all(|d| (1..=6).contains(d)));
+}
+
+#[test]
+fn seeded_dice_sequence_is_stable() {
+    // Changing these breaks replaying games from stored seeds.
+    assert_eq!(
+        rolls(&mut SeededDice::new(0), 5),
+        [[2, 1], [2, 5], [2, 1], [6, 3], [6, 3]]
+    );
+}
+
+#[test]
+fn seeded_dice_are_roughly_uniform() {
+    let mut counts = [0; 6];
+    for d in rolls(&mut SeededDice::new(99), 60000).iter().flatten() {
+        counts[*d as usize - 1] += 1;
+    }
+
+    assert!(
+        counts.iter().all(|&n| (19000..21000).contains(&n)),
+        "{counts:?}"
+    );
+}
+
+#[test]
+fn scripted_dice_parse_and_replay() {
+    let mut dice: ScriptedDice =
+        "31\n# comment\n\n6-6\n2 5  # trailing\n".parse().unwrap();
+
+    assert_eq!(dice.remaining(), 3);
+    assert_eq!(rolls(&mut dice, 3), [[3, 1], [6, 6], [2, 5]]);
+    assert_eq!(dice.remaining(), 0);
+}
+
+#[test]
+fn scripted_dice_reject_bad_rolls() {
+    let err = "31\n71\n".parse::<ScriptedDice>().unwrap_err();
+    assert_eq!(err.line_number, 2);
+
+    assert!("123".parse::<ScriptedDice>().is_err());
+    assert!("1".parse::<ScriptedDice>().is_err());
+}
+
+#[test]
+#[should_panic(expected = "ran out of rolls")]
+fn scripted_dice_panic_when_exhausted() {
+    let mut dice = ScriptedDice::new([[1, 2]]);
+    dice.roll();
+    dice.roll();
+}
+
+#[test]
+fn file_dice_read_lazily() {
+    let mut dice = FileDice::new(Cursor::new("43\n\n21\n"));
+
+    assert_eq!(dice.try_roll().unwrap(), Some([4, 3]));
+    assert_eq!(dice.roll(), [2, 1]);
+    assert_eq!(dice.try_roll().unwrap(), None);
+
+    let mut dice = FileDice::new(Cursor::new("43\nxx\n"));
+    dice.roll();
+    assert!(dice.try_roll().is_err());
+}
+
+#[test]
+fn games_replay_from_a_dice_file() {
+    let record = play_game(
+        [&mut PipCountPlayer, &mut PipCountPlayer],
+        &mut SeededDice::new(17),
+    );
+
+    // The opening roll is stored as [light, dark], which replays the same
+    // way as long as it is not a double.
+    let script: String = record
+        .turns
+        .iter()
+        .map(|t| format!("{}{}\n", t.dice[0], t.dice[1]))
+        .collect();
+
+    let path = std::env::temp_dir().join("backgammon-sim-replay-dice.txt");
+    fs::write(&path, script).unwrap();
+
+    let mut dice = FileDice::open(&path).unwrap();
+    let replayed =
+        play_game([&mut PipCountPlayer, &mut PipCountPlayer], &mut dice);
+
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(record, replayed);
+}
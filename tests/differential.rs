@@ -0,0 +1,130 @@
+mod common;
+
+use backgammon_sim::{differential, rng::Rng, Backgammon, GameState};
+use common::position;
+
+#[test]
+fn engines_agree_on_random_positions() {
+    let mismatches = differential::run(&mut Rng::new(3), 100);
+
+    assert!(
+        mismatches.is_empty(),
+        "{}\n{} mismatches",
+        mismatches[0],
+        mismatches.len()
+    );
+}
+
+#[test]
+fn engines_agree_on_the_opening() {
+    let state = GameState::new_with_default_setup();
+
+    for turn in [true, false] {
+        for d1 in 1..=6 {
+            for d2 in d1..=6 {
+                assert_eq!(differential::compare(&state, turn, [d1, d2]), None);
+            }
+        }
+    }
+}
+
+// Positions where `enum_based` used to disagree before its rules were fixed.
+#[test]
+fn engines_agree_on_fixed_rules() {
+    let cases = [
+        // Entering from the bar only on the point matching the die.
+        (
+            "Light enters",
+            position(&[(0, 14), (12, -15)], [1, 0], [0, 0]),
+            true,
+            [2, 4],
+        ),
+        (
+            "Dark enters",
+            position(&[(23, -14), (11, 15)], [0, 1], [0, 0]),
+            false,
+            [2, 4],
+        ),
+        // Bearing off with the exact die.
+        (
+            "Light bears off",
+            position(&[(20, 7), (19, 8), (3, -15)], [0, 0], [0, 0]),
+            true,
+            [4, 4],
+        ),
+        (
+            "Dark bears off",
+            position(&[(3, -7), (4, -8), (20, 15)], [0, 0], [0, 0]),
+            false,
+            [4, 4],
+        ),
+        // Bearing off with a larger die from the furthest checker only.
+        (
+            "Light larger die",
+            position(&[(21, 2), (23, 3), (5, -15)], [0, 0], [10, 0]),
+            true,
+            [6, 5],
+        ),
+        (
+            "Dark larger die",
+            position(&[(2, -2), (0, -3), (18, 15)], [0, 0], [0, 10]),
+            false,
+            [6, 5],
+        ),
+        (
+            "Light furthest first",
+            position(&[(19, 1), (21, 2), (5, -15)], [0, 0], [12, 0]),
+            true,
+            [6, 6],
+        ),
+        (
+            "Light checker further out",
+            position(&[(18, 1), (21, 1), (5, -15)], [0, 0], [13, 0]),
+            true,
+            [5, 1],
+        ),
+        (
+            "Dark checker further out",
+            position(&[(5, -1), (2, -1), (18, 15)], [0, 0], [0, 13]),
+            false,
+            [5, 1],
+        ),
+        (
+            "Light not home",
+            position(&[(17, 1), (20, 1), (5, -15)], [0, 0], [13, 0]),
+            true,
+            [6, 5],
+        ),
+        // Each player's home board decides only whether they can bear off.
+        (
+            "Dark in Light's home",
+            position(&[(20, 14), (19, -1), (5, -14)], [0, 0], [1, 0]),
+            true,
+            [4, 4],
+        ),
+        (
+            "Light in Dark's home",
+            position(&[(3, -14), (4, 1), (18, 14)], [0, 0], [0, 1]),
+            false,
+            [4, 4],
+        ),
+        // The second die moves from the position after the first.
+        (
+            "Light runs",
+            position(&[(0, 1), (23, 14), (3, -15)], [0, 0], [0, 0]),
+            true,
+            [6, 5],
+        ),
+        (
+            "Dark runs",
+            position(&[(23, -1), (0, -14), (20, 15)], [0, 0], [0, 0]),
+            false,
+            [6, 5],
+        ),
+    ];
+
+    for (name, state, turn, dice) in cases {
+        GameState::validate(&state).unwrap();
+        assert_eq!(differential::compare(&state, turn, dice), None, "{name}");
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use backgammon_sim::{enum_based::GameState, Backgammon};
+use common::position;
+
+const BAR: u8 = 99;
+const OFF: u8 = 99;
+
+#[test]
+fn enters_only_on_the_point_of_the_die() {
+    let light: GameState = position(&[(0, 14), (12, -15)], [1, 0], [0, 0]);
+    assert_eq!(light.legal_moves(true, 4), [[BAR, 3]]);
+
+    let dark: GameState = position(&[(23, -14), (11, 15)], [0, 1], [0, 0]);
+    assert_eq!(dark.legal_moves(false, 4), [[BAR, 20]]);
+}
+
+#[test]
+fn bears_off_from_the_point_of_the_die() {
+    let light: GameState =
+        position(&[(20, 7), (19, 8), (3, -15)], [0, 0], [0, 0]);
+    let moves = light.legal_moves(true, 4);
+    assert!(moves.contains(&[20, OFF]));
+    assert!(!moves.contains(&[19, OFF]));
+
+    let dark: GameState =
+        position(&[(3, -7), (4, -8), (20, 15)], [0, 0], [0, 0]);
+    let moves = dark.legal_moves(false, 4);
+    assert!(moves.contains(&[3, OFF]));
+    assert!(!moves.contains(&[4, OFF]));
+}
+
+#[test]
+fn bears_off_the_furthest_checker_with_a_larger_die() {
+    let light: GameState =
+        position(&[(21, 2), (22, 1), (23, 2), (5, -15)], [0, 0], [10, 0]);
+    assert_eq!(light.legal_moves(true, 6), [[21, OFF]]);
+
+    let dark: GameState =
+        position(&[(2, -2), (1, -1), (0, -2), (18, 15)], [0, 0], [0, 10]);
+    assert_eq!(dark.legal_moves(false, 6), [[2, OFF]]);
+}
+
+#[test]
+fn larger_die_must_move_checkers_further_out() {
+    let light: GameState =
+        position(&[(18, 1), (21, 1), (5, -15)], [0, 0], [13, 0]);
+    assert_eq!(light.legal_moves(true, 5), [[18, 23]]);
+
+    let dark: GameState =
+        position(&[(5, -1), (2, -1), (18, 15)], [0, 0], [0, 13]);
+    assert_eq!(dark.legal_moves(false, 5), [[5, 0]]);
+}
+
+#[test]
+fn opponent_checkers_do_not_stop_bearing_off() {
+    let light: GameState =
+        position(&[(20, 14), (19, -1), (5, -14)], [0, 0], [1, 0]);
+    assert!(light.legal_moves(true, 4).contains(&[20, OFF]));
+
+    let dark: GameState =
+        position(&[(3, -14), (4, 1), (18, 14)], [0, 0], [0, 1]);
+    assert!(dark.legal_moves(false, 4).contains(&[3, OFF]));
+}
+
+#[test]
+fn second_die_moves_from_the_new_position() {
+    let light: GameState =
+        position(&[(0, 1), (23, 14), (3, -15)], [0, 0], [0, 0]);
+    assert_eq!(
+        light.legal_plays(true, [6, 5]),
+        [position(&[(11, 1), (23, 14), (3, -15)], [0, 0], [0, 0])]
+    );
+
+    let dark: GameState =
+        position(&[(23, -1), (0, -14), (20, 15)], [0, 0], [0, 0]);
+    assert_eq!(
+        dark.legal_plays(false, [6, 5]),
+        [position(&[(12, -1), (0, -14), (20, 15)], [0, 0], [0, 0])]
+    );
+}
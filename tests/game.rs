@@ -0,0 +1,103 @@
+use std::io::Cursor;
+
+use backgammon_sim::{
+    dice::{ScriptedDice, SeededDice},
+    game::{
+        play_game, roll_opening, EvaluatorPlayer, HumanPlayer, PipCountPlayer,
+        Player, RandomPlayer,
+    },
+    rng::Rng,
+    Backgammon, GameState, MoveBuffer,
+};
+
+fn checker_count(state: &GameState) -> [u32; 2] {
+    let mut count = [0; 2];
+    for i in 0..24 {
+        let n = state.checkers(i);
+        count[(n < 0) as usize] += n.unsigned_abs() as u32;
+    }
+    let [bar, off] = [state.bar(), state.borne_off()];
+    [0, 1].map(|p| count[p] + (bar[p] + off[p]) as u32)
+}
+
+#[test]
+fn random_games_follow_the_rules() {
+    let mut dice = SeededDice::new(42);
+    let mut light = RandomPlayer::new(Rng::new(1));
+    let mut dark = RandomPlayer::new(Rng::new(2));
+
+    let mut moves = MoveBuffer::new();
+
+    for _ in 0..20 {
+        let record = play_game([&mut light, &mut dark], &mut dice);
+
+        let mut state = record.start;
+        for (i, turn) in record.turns.iter().enumerate() {
+            if i > 0 {
+                assert_ne!(turn.turn, record.turns[i - 1].turn);
+            }
+
+            moves.generate(turn.turn, state, turn.dice);
+            assert!(moves.state_iterator().any(|s| s == turn.state));
+            assert_eq!(checker_count(&turn.state), [15, 15]);
+
+            state = turn.state;
+        }
+
+        assert_eq!(record.final_state().result(), Some(record.result));
+        assert_eq!(record.turns.last().unwrap().turn, record.result.winner);
+    }
+}
+
+#[test]
+fn games_are_reproducible() {
+    let play = || {
+        let mut light = RandomPlayer::new(Rng::new(3));
+        let mut dark = PipCountPlayer;
+        play_game([&mut light, &mut dark], &mut SeededDice::new(7))
+    };
+
+    assert_eq!(play(), play());
+}
+
+#[test]
+fn opening_roll_rerolls_doubles() {
+    let mut dice = ScriptedDice::new([[2, 2], [6, 6], [3, 5], [4, 1]]);
+
+    assert_eq!(roll_opening(&mut dice), (false, [3, 5]));
+    assert_eq!(roll_opening(&mut dice), (true, [4, 1]));
+}
+
+#[test]
+fn evaluator_player_beats_random() {
+    let mut dice = SeededDice::new(5);
+    let mut light = EvaluatorPlayer::new(1);
+    let mut dark = RandomPlayer::new(Rng::new(6));
+
+    let light_wins = (0..10)
+        .filter(|_| play_game([&mut light, &mut dark], &mut dice).result.winner)
+        .count();
+
+    assert!(light_wins >= 8, "{light_wins}");
+}
+
+#[test]
+fn human_player_reads_choice() {
+    let state = GameState::new_with_default_setup();
+
+    let mut moves = MoveBuffer::new();
+    moves.generate(true, state, [6, 5]);
+    let options: Vec<_> = moves.play_iterator().collect();
+
+    let mut output = Vec::new();
+    let mut human =
+        HumanPlayer::new(Cursor::new("nonsense\n999\n1\n"), &mut output);
+
+    assert_eq!(human.choose_move(&moves), options[1].1);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Light rolled [5, 6]"));
+    assert!(output.contains("24/13"));
+    assert!(output
+        .contains(&format!("Enter a number from 0 to {}", options.len() - 1)));
+}
@@ -0,0 +1,83 @@
+use backgammon_sim::{
+    enum_based, probabilities::Probabilities, Backgammon, Evaluator,
+    GameResult, GameState, WinKind,
+};
+
+fn light_won<B: Backgammon>(dark: &[(usize, i8)], bar: u8, off: u8) -> B {
+    let mut checkers = [0; 24];
+    for &(i, n) in dark {
+        checkers[i] = -n;
+    }
+
+    B::from_parts(checkers, [0, bar], [15, off])
+}
+
+fn check_results<B: Backgammon>() {
+    assert_eq!(B::default_setup().result(), None);
+
+    let single = light_won::<B>(&[(3, 14)], 0, 1);
+    assert_eq!(
+        single.result(),
+        Some(GameResult {
+            winner: true,
+            kind: WinKind::Single
+        })
+    );
+
+    let gammon = light_won::<B>(&[(3, 10), (12, 5)], 0, 0);
+    assert_eq!(gammon.result().map(|r| r.kind), Some(WinKind::Gammon));
+    assert_eq!(gammon.result().unwrap().points(), 2);
+
+    let on_bar = light_won::<B>(&[(3, 14)], 1, 0);
+    assert_eq!(on_bar.result().map(|r| r.kind), Some(WinKind::Backgammon));
+
+    let in_home = light_won::<B>(&[(3, 14), (20, 1)], 0, 0);
+    assert_eq!(in_home.result().map(|r| r.kind), Some(WinKind::Backgammon));
+    assert_eq!(in_home.result().unwrap().points(), 3);
+
+    let mut checkers = [0; 24];
+    checkers[22] = 2;
+    let dark_won = B::from_parts(checkers, [0, 0], [13, 15]);
+    assert_eq!(
+        dark_won.result(),
+        Some(GameResult {
+            winner: false,
+            kind: WinKind::Single
+        })
+    );
+}
+
+#[test]
+fn i8_based_results() {
+    check_results::<GameState>();
+}
+
+#[test]
+fn enum_based_results() {
+    check_results::<enum_based::GameState>();
+}
+
+#[test]
+fn evaluator_stops_at_end_of_game() {
+    let mut ev = Evaluator::new();
+
+    let gammon = light_won::<GameState>(&[(3, 10), (12, 5)], 0, 0);
+    let eval = ev.get_brute_force_eval(gammon, false, 2);
+    assert_eq!(
+        eval,
+        Probabilities {
+            lose_gammon: 1.0,
+            ..Probabilities::single(0.0)
+        }
+    );
+    assert_eq!(eval.cubeless_equity(), -2.0);
+
+    // Light bears off the last two checkers with any roll.
+    let mut checkers = [0; 24];
+    checkers[23] = 2;
+    checkers[20] = -15;
+    let almost = GameState::from_parts(checkers, [0, 0], [13, 0]);
+    let eval = ev.get_brute_force_eval(almost, true, 3);
+    assert!((eval.win_backgammon - 1.0).abs() < 1e-9);
+    assert!((eval.cubeless_equity() - 3.0).abs() < 1e-9);
+}
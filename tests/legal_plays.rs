@@ -0,0 +1,202 @@
+mod common;
+
+use std::collections::HashSet;
+
+use backgammon_sim::{
+    i8_based::notation::format_play, Backgammon, GameState, MoveBuffer,
+    SPECIAL_MOVE,
+};
+use common::{position, sample_positions};
+
+// The same position with the colours swapped, so Dark faces what Light did.
+fn mirrored(state: &GameState) -> GameState {
+    let tiles = std::array::from_fn(|i| -state.checkers(23 - i));
+    let [light_bar, dark_bar] = state.bar();
+    let [light_off, dark_off] = state.borne_off();
+    GameState::from_parts(tiles, [dark_bar, light_bar], [dark_off, light_off])
+}
+
+fn formatted_plays(state: GameState, turn: bool, dice: [u8; 2]) -> Vec<String> {
+    let mut moves = MoveBuffer::new();
+    moves.generate(turn, state, dice);
+
+    let mut plays: Vec<_> = moves
+        .play_iterator()
+        .map(|(play, _)| format_play(state, turn, &play))
+        .collect();
+    plays.sort();
+    plays
+}
+
+// Every position reachable by trying each checker with each die in turn,
+// keeping only the plays that use as many dice as possible and, if only one
+// of two different dice can be used, the larger one.
+fn reference_states(
+    state: GameState,
+    turn: bool,
+    dice: [u8; 2],
+) -> HashSet<GameState> {
+    fn walk(
+        state: GameState,
+        turn: bool,
+        dice: &[u8],
+        used: &mut Vec<u8>,
+        out: &mut Vec<(Vec<u8>, GameState)>,
+    ) {
+        out.push((used.clone(), state));
+        for (i, &die) in dice.iter().enumerate() {
+            for from in (0..24).chain([SPECIAL_MOVE]) {
+                if let Ok(next) = state.do_move(turn, from, die) {
+                    let mut rest = dice.to_vec();
+                    rest.remove(i);
+                    used.push(die);
+                    walk(next, turn, &rest, used, out);
+                    used.pop();
+                }
+            }
+        }
+    }
+
+    let all_dice = if dice[0] == dice[1] {
+        vec![dice[0]; 4]
+    } else {
+        dice.to_vec()
+    };
+
+    let mut reached = Vec::new();
+    walk(state, turn, &all_dice, &mut Vec::new(), &mut reached);
+
+    let most = reached.iter().map(|(used, _)| used.len()).max().unwrap();
+    reached.retain(|(used, _)| used.len() == most);
+
+    let larger = dice[0].max(dice[1]);
+    if most == 1 && reached.iter().any(|(used, _)| used[0] == larger) {
+        reached.retain(|(used, _)| used[0] == larger);
+    }
+
+    reached.into_iter().map(|(_, state)| state).collect()
+}
+
+// Positions where the dice rules decide what is legal, with the plays Light
+// may make. Dark gets the mirrored position and must have the same choices.
+fn corpus() -> Vec<(&'static str, GameState, [u8; 2], Vec<&'static str>)> {
+    vec![
+        (
+            "only one die can be played, either would do alone",
+            position(&[(0, 1), (3, -2), (23, 14)], [0, 0], [0, 13]),
+            [2, 1],
+            vec!["24/22"],
+        ),
+        (
+            "only the smaller die can be played",
+            position(&[(0, 1), (6, -2), (7, -2), (23, 14)], [0, 0], [0, 11]),
+            [6, 1],
+            vec!["24/23"],
+        ),
+        (
+            "both dice fit only with the smaller one first",
+            position(&[(0, 1), (6, -2), (23, 14)], [0, 0], [0, 13]),
+            [6, 1],
+            vec!["24/17"],
+        ),
+        (
+            "both dice fit only with the larger one first",
+            position(&[(0, 1), (1, -2), (23, 14)], [0, 0], [0, 13]),
+            [6, 1],
+            vec!["24/17"],
+        ),
+        (
+            "one checker using both dice is the same as two",
+            position(
+                &[(0, 1), (5, 1), (6, -2), (10, -2), (23, 13)],
+                [0, 0],
+                [0, 11],
+            ),
+            [6, 5],
+            vec!["19/8", "24/13"],
+        ),
+        (
+            "entering with the smaller die, then moving on",
+            position(&[(11, 14), (5, -2), (22, -13)], [1, 0], [0, 0]),
+            [6, 5],
+            vec!["bar/14", "bar/20 13/7"],
+        ),
+        (
+            "entering with the larger die, then moving on",
+            position(&[(11, 14), (4, -2), (22, -13)], [1, 0], [0, 0]),
+            [6, 5],
+            vec!["bar/14", "bar/19 13/8"],
+        ),
+        (
+            "a second checker on the bar blocks the other die",
+            position(&[(11, 13), (5, -2), (22, -13)], [2, 0], [0, 0]),
+            [6, 5],
+            vec!["bar/20"],
+        ),
+        (
+            "nothing enters",
+            position(&[(11, 14), (4, -2), (5, -2), (22, -11)], [1, 0], [0, 0]),
+            [6, 5],
+            vec![""],
+        ),
+        (
+            "only three of four moves of a double",
+            position(&[(0, 1), (12, -2), (23, 14)], [0, 0], [0, 13]),
+            [3, 3],
+            vec!["24/15"],
+        ),
+        (
+            "a double where only the entered checker can move on",
+            position(&[(0, 2), (2, -2), (23, 12)], [1, 0], [0, 13]),
+            [2, 2],
+            vec!["bar/17"],
+        ),
+        (
+            "bearing off with the larger die once the smaller brings a \
+             checker home",
+            position(&[(17, 1), (21, 1), (2, -15)], [0, 0], [13, 0]),
+            [6, 1],
+            vec!["7/1 3/2", "7/off"],
+        ),
+    ]
+}
+
+#[test]
+fn corpus_plays_are_exactly_the_legal_ones() {
+    for (name, state, dice, expected) in corpus() {
+        let mut expected: Vec<_> =
+            expected.into_iter().map(String::from).collect();
+        expected.sort();
+
+        assert_eq!(formatted_plays(state, true, dice), expected, "{name}");
+        assert_eq!(
+            formatted_plays(mirrored(&state), false, dice),
+            expected,
+            "{name}, as Dark"
+        );
+    }
+}
+
+#[test]
+fn generated_positions_match_reference() {
+    let mut moves = MoveBuffer::new();
+
+    let corpus = corpus().into_iter().flat_map(|(_, state, _, _)| {
+        [(state, true), (mirrored(&state), false)]
+    });
+
+    for (state, turn) in corpus.chain(sample_positions(21, 2)) {
+        for d1 in 1..=6 {
+            for d2 in d1..=6 {
+                moves.generate(turn, state, [d1, d2]);
+
+                let states: HashSet<_> = moves.state_iterator().collect();
+                assert_eq!(
+                    states,
+                    reference_states(state, turn, [d1, d2]),
+                    "{state}\nturn: {turn}, dice: {d1}-{d2}"
+                );
+            }
+        }
+    }
+}
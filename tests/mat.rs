@@ -0,0 +1,178 @@
+use backgammon_sim::{
+    dice::SeededDice,
+    game::{
+        mat::{MatErrorKind, MatchFile},
+        play_game, Action, PipCountPlayer, RandomPlayer,
+    },
+    rng::Rng,
+    GameState,
+};
+
+const CUBE_GAME: &str = "\
+; [Site \"example\"]
+ 3 point match
+
+ Game 1
+ Alice : 0                       Bob Smith : 0
+  1)                             31: 8/5 6/5
+  2) 52: 13/11 13/8               Doubles => 2
+  3)  Takes                      64: 24/18 13/9
+  4)  Doubles => 4               Drops
+      Wins 2 points
+";
+
+fn game_text(rows: &str) -> String {
+    format!(" 1 point match\n\n Game 1\n A : 0    B : 0\n{rows}")
+}
+
+#[test]
+fn reads_cube_actions_and_columns() {
+    let file: MatchFile = CUBE_GAME.parse().unwrap();
+
+    assert_eq!(file.match_length, 3);
+    assert_eq!(file.names, ["Alice", "Bob Smith"]);
+    assert_eq!(file.games.len(), 1);
+
+    let game = &file.games[0];
+    assert_eq!(game.score, [0, 0]);
+    assert_eq!(game.winner, Some((true, 2)));
+
+    let players: Vec<_> = game.actions.iter().map(Action::player).collect();
+    assert_eq!(players, [false, true, false, true, false, true, false]);
+    assert_eq!(
+        game.actions[2],
+        Action::Double {
+            player: false,
+            value: 2
+        }
+    );
+    assert_eq!(game.actions[6], Action::Drop(false));
+
+    let state = game.final_state();
+    assert_ne!(state, GameState::new_with_default_setup());
+    assert_eq!(file.to_string().parse(), Ok(file));
+}
+
+#[test]
+fn self_play_round_trips() {
+    let mut dice = SeededDice::new(5);
+    let mut light = RandomPlayer::new(Rng::new(6));
+    let mut dark = PipCountPlayer;
+
+    let records: Vec<_> = (0..3)
+        .map(|_| play_game([&mut light, &mut dark], &mut dice))
+        .collect();
+
+    let file = MatchFile::from_records(0, ["Random", "Pip count"], &records);
+    let parsed: MatchFile = file.to_string().parse().unwrap();
+
+    assert_eq!(parsed, file);
+    for (game, record) in parsed.games.iter().zip(&records) {
+        assert_eq!(game.final_state(), record.final_state());
+    }
+}
+
+#[test]
+fn rejects_illegal_games() {
+    let kind = |text: &str| {
+        let e = text.parse::<MatchFile>().unwrap_err();
+        (e.line, e.kind)
+    };
+
+    assert_eq!(kind(" Game 1\n"), (1, MatErrorKind::Header));
+
+    // 8/4 needs a four.
+    assert!(matches!(
+        kind(&game_text("  1) 31: 8/4 6/5\n")),
+        (5, MatErrorKind::Play(_))
+    ));
+    assert_eq!(
+        kind(&game_text("  1) 31: 8/5 6/5\n  2) 42: 8/4 6/4\n")),
+        (6, MatErrorKind::OutOfTurn)
+    );
+    assert_eq!(kind(&game_text("  1)  Takes\n")), (5, MatErrorKind::Cube));
+    assert_eq!(
+        kind(&game_text(
+            "  1) 31: 8/5 6/5                 Doubles => 2\n  \
+             2)  Drops\n      \
+             Wins 1 point\n"
+        )),
+        (7, MatErrorKind::Winner)
+    );
+    assert_eq!(
+        kind(&game_text("  1) hello\n")),
+        (5, MatErrorKind::Syntax("1) hello".to_owned()))
+    );
+}
+
+#[test]
+fn rejects_doubles_out_of_turn() {
+    let kind = |rows: &str| {
+        let e = game_text(rows).parse::<MatchFile>().unwrap_err();
+        (e.line, e.kind)
+    };
+
+    // Before the opening roll.
+    assert_eq!(kind("  1)  Doubles => 2\n"), (5, MatErrorKind::OutOfTurn));
+    // Light has just rolled, so Dark is on roll.
+    assert_eq!(
+        kind("  1) 31: 8/5 6/5\n  2)  Doubles => 2\n"),
+        (6, MatErrorKind::OutOfTurn)
+    );
+}
+
+#[test]
+fn rejects_doubles_in_the_crawford_game() {
+    let text = " 3 point match
+
+ Game 1
+ A : 2                           B : 0
+  1) 31: 8/5 6/5                 Doubles => 2
+";
+    let e = text.parse::<MatchFile>().unwrap_err();
+    assert_eq!((e.line, e.kind), (5, MatErrorKind::Cube));
+
+    // Either player may double after the Crawford game.
+    let file: MatchFile = " 3 point match
+
+ Game 1
+ A : 2                           B : 0
+  1) 31: 8/5 6/5                 64: 24/18 13/9
+                                 Wins 1 point
+
+ Game 2
+ A : 2                           B : 1
+  1) 31: 8/5 6/5                 Doubles => 2
+  2)  Takes                      64: 24/18 13/9
+  3)  Doubles => 4
+"
+    .parse()
+    .unwrap();
+    assert_eq!(file.games[1].actions.len(), 5);
+}
+
+#[test]
+fn reads_beavers() {
+    let file: MatchFile = game_text(
+        "  1) 31: 8/5 6/5                 Doubles => 2\n  \
+         2)  Beavers                    64: 24/18 13/9\n  \
+         3)  Doubles => 8               Drops\n      \
+         Wins 4 points\n",
+    )
+    .parse()
+    .unwrap();
+
+    let game = &file.games[0];
+    assert_eq!(game.actions[2], Action::Beaver(true));
+    assert_eq!(game.winner, Some((true, 4)));
+    assert_eq!(file.to_string().parse(), Ok(file));
+
+    // Dark no longer owns the cube.
+    let e = game_text(
+        "  1) 31: 8/5 6/5                 Doubles => 2\n  \
+         2)  Beavers                    Doubles => 8\n",
+    )
+    .parse::<MatchFile>()
+    .unwrap_err();
+    assert_eq!((e.line, e.kind), (6, MatErrorKind::Cube));
+}
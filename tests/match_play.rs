@@ -0,0 +1,194 @@
+use backgammon_sim::{
+    cube::{Cube, CubeResponse},
+    dice::SeededDice,
+    game::{
+        mat::MatchFile, Action, CubefulRecord, Match, MatchGame, MatchRules,
+        PipCountPlayer, Player, RandomPlayer,
+    },
+    rng::Rng,
+    Backgammon, GameState, MoveBuffer, WinKind,
+};
+
+// Doubles when well ahead in the race and always takes.
+struct CubeHappy;
+
+impl Player for CubeHappy {
+    fn choose_move(&mut self, moves: &MoveBuffer) -> GameState {
+        PipCountPlayer.choose_move(moves)
+    }
+
+    fn offer_double(&mut self, state: GameState, turn: bool, _: Cube) -> bool {
+        let [light, dark] = state.pip_counts().map(|p| p as i32);
+        (if turn { dark - light } else { light - dark }) > 10
+    }
+
+    fn answer_double(
+        &mut self,
+        _: GameState,
+        _: bool,
+        _: Cube,
+        _: bool,
+    ) -> CubeResponse {
+        CubeResponse::Take
+    }
+}
+
+// A game won by a dropped double, worth `points`.
+fn game(
+    score: [u64; 2],
+    crawford: bool,
+    winner: bool,
+    points: u32,
+) -> MatchGame {
+    MatchGame {
+        score,
+        crawford,
+        record: CubefulRecord {
+            start: GameState::new_with_default_setup(),
+            actions: Vec::new(),
+            cube: Cube::new(),
+            winner,
+            result: None,
+        },
+        points,
+    }
+}
+
+fn gammon(game: &MatchGame) -> bool {
+    game.record
+        .result
+        .is_some_and(|result| result.kind != WinKind::Single)
+}
+
+fn doubles(actions: &[Action]) -> impl Iterator<Item = bool> + '_ {
+    actions.iter().filter_map(|action| match action {
+        Action::Double { player, .. } => Some(*player),
+        _ => None,
+    })
+}
+
+#[test]
+fn follows_the_crawford_rule() {
+    let [mut crawford_games, mut post_crawford_doubles] = [0, 0];
+
+    for seed in 0..20 {
+        let mut m = Match::new(MatchRules::to(5));
+        let winner = m
+            .play([&mut CubeHappy, &mut CubeHappy], &mut SeededDice::new(seed));
+
+        let score = m.score();
+        assert_eq!(m.winner(), Some(winner));
+        assert!(score[(!winner) as usize] >= 5);
+
+        let mut crawford_seen = false;
+        for game in &m.games {
+            let leader = match game.score.map(|s| s == 4) {
+                [true, false] => Some(true),
+                [false, true] => Some(false),
+                _ => None,
+            };
+
+            assert_eq!(game.crawford, leader.is_some() && !crawford_seen);
+            if game.crawford {
+                crawford_seen = true;
+                crawford_games += 1;
+                assert_eq!(doubles(&game.record.actions).count(), 0);
+            } else if leader.is_some() {
+                post_crawford_doubles += doubles(&game.record.actions).count();
+            }
+        }
+    }
+
+    assert!(crawford_games > 0);
+    assert!(post_crawford_doubles > 0);
+}
+
+#[test]
+fn only_the_crawford_game_turns_off_doubling() {
+    let mut m = Match::new(MatchRules::to(3));
+    m.games.push(game([0, 0], false, true, 2));
+    assert!(m.is_crawford());
+    assert_eq!(m.cube_rules().doubling, [false, false]);
+
+    m.games.push(game([2, 0], true, false, 1));
+    assert!(!m.is_crawford());
+    assert_eq!(m.cube_rules().doubling, [true, true]);
+
+    // Without the Crawford rule the leader's opponent may double at once.
+    let mut m = Match::new(MatchRules {
+        crawford: false,
+        ..MatchRules::to(3)
+    });
+    m.games.push(game([0, 0], false, true, 2));
+    assert_eq!(m.cube_rules().doubling, [true, true]);
+}
+
+#[test]
+fn scores_do_not_overflow() {
+    // A backgammon on the highest cube.
+    let points = Cube::MAX_VALUE * 3;
+
+    let mut m = Match::new(MatchRules::money());
+    for i in 0..3 {
+        let score = m.score();
+        m.games.push(game(score, false, i != 1, points));
+    }
+    assert_eq!(m.score(), [2 * u64::from(points), u64::from(points)]);
+}
+
+#[test]
+fn matches_write_mat_files() {
+    let mut m = Match::new(MatchRules::to(3));
+    m.play(
+        [&mut CubeHappy, &mut RandomPlayer::new(Rng::new(1))],
+        &mut SeededDice::new(4),
+    );
+
+    let file = MatchFile::from_match(["Cube", "Random"], &m);
+    assert_eq!(file.match_length, 3);
+    assert_eq!(file.games.len(), m.games.len());
+    assert_eq!(file.to_string().parse(), Ok(file));
+}
+
+#[test]
+fn jacoby_rule_needs_a_turned_cube() {
+    let play = |jacoby| {
+        let mut m = Match::new(MatchRules {
+            jacoby,
+            ..MatchRules::money()
+        });
+        let mut dice = SeededDice::new(9);
+        for _ in 0..40 {
+            m.play_game(
+                [&mut PipCountPlayer, &mut RandomPlayer::new(Rng::new(2))],
+                &mut dice,
+            );
+        }
+        assert_eq!(m.winner(), None);
+        m
+    };
+
+    let with = play(true);
+    assert!(with.games.iter().any(gammon));
+    assert!(with.games.iter().all(|g| g.points == 1));
+
+    let without = play(false);
+    assert!(without
+        .games
+        .iter()
+        .filter(|g| gammon(g))
+        .all(|g| g.points > 1));
+}
+
+#[test]
+fn beavers_only_in_money_play() {
+    assert!(Match::new(MatchRules::money()).cube_rules().beavers);
+    assert!(
+        !Match::new(MatchRules {
+            beavers: true,
+            ..MatchRules::to(7)
+        })
+        .cube_rules()
+        .beavers
+    );
+}
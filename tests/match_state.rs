@@ -0,0 +1,83 @@
+use backgammon_sim::{
+    match_state::{GamePhase, MatchIdError, MatchState},
+    GameState, WinKind,
+};
+
+#[test]
+fn decodes_a_money_game() {
+    let state = MatchState::from_ids("4HPwATDgc/ABMA", "cAkAAAAAAAAA").unwrap();
+
+    assert_eq!(state.position, GameState::new_with_default_setup());
+    assert_eq!(state.cube_value, 1);
+    assert_eq!(state.cube_owner, None);
+    // Player 1, who is Dark.
+    assert!(!state.on_roll);
+    assert!(!state.turn);
+    assert_eq!(state.phase, GamePhase::Playing);
+    assert_eq!(state.dice, None);
+    assert_eq!(state.match_length, 0);
+    assert_eq!(state.score, [0, 0]);
+
+    assert_eq!(state, MatchState::new(state.position, false, 0));
+    assert_eq!(state.match_id(), "cAkAAAAAAAAA");
+}
+
+#[test]
+fn decodes_a_match_in_progress() {
+    let state: MatchState = "4HPwATDgc/ABMA:QYkqASAAIAAA".parse().unwrap();
+
+    assert_eq!(state.cube_value, 2);
+    assert_eq!(state.cube_owner, Some(true));
+    assert!(!state.on_roll);
+    assert!(!state.crawford);
+    assert_eq!(state.phase, GamePhase::Playing);
+    assert_eq!(state.dice, Some([5, 2]));
+    assert_eq!(state.match_length, 9);
+    assert_eq!(state.score, [2, 4]);
+
+    assert_eq!(state.gnubg_id(), "4HPwATDgc/ABMA:QYkqASAAIAAA");
+}
+
+#[test]
+fn round_trips_every_field() {
+    let mut state =
+        MatchState::new(GameState::new_with_default_setup(), true, 7);
+    state.cube_value = 8;
+    state.cube_owner = Some(false);
+    state.turn = false;
+    state.crawford = true;
+    state.phase = GamePhase::Resigned;
+    state.double_offered = true;
+    state.resignation = Some(WinKind::Gammon);
+    state.dice = Some([6, 3]);
+    state.score = [6, 3];
+
+    let id = state.match_id();
+    assert_eq!(id.len(), 12);
+    assert_eq!(state.gnubg_id().parse(), Ok(state));
+}
+
+#[test]
+fn rejects_malformed_ids() {
+    let position = "4HPwATDgc/ABMA";
+
+    assert_eq!(
+        MatchState::from_ids(position, "cAkAAAAAAAA"),
+        Err(MatchIdError::Length(11))
+    );
+    assert_eq!(
+        MatchState::from_ids(position, "cAkAAAAAAA!A"),
+        Err(MatchIdError::Character('!'))
+    );
+    assert_eq!(
+        "4HPwATDgc/ABMA cAkAAAAAAAAA".parse::<MatchState>(),
+        Err(MatchIdError::Separator)
+    );
+    let mut state =
+        MatchState::new(GameState::new_with_default_setup(), true, 0);
+    state.dice = Some([7, 0]);
+    assert_eq!(
+        MatchState::from_ids(position, &state.match_id()),
+        Err(MatchIdError::Dice([7, 0]))
+    );
+}
@@ -0,0 +1,133 @@
+use backgammon_sim::{
+    match_state::MatchState,
+    met::{MatchEquityTable, MetErrorKind},
+    probabilities::Probabilities,
+    GameState,
+};
+
+const TABLE: &str = "\
+# A three point table
+name Example
+pre-crawford
+50.00 67.90 75.19
+32.10 50.00 59.61   # 2-away
+24.81 40.39 50.00
+post-crawford
+50.00 51.22 69.14
+";
+
+fn state(length: u16, score: [u16; 2]) -> MatchState {
+    MatchState {
+        score,
+        ..MatchState::new(GameState::new_with_default_setup(), true, length)
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn reads_and_writes_tables() {
+    let table: MatchEquityTable = TABLE.parse().unwrap();
+
+    assert_eq!(table.name, "Example");
+    assert_eq!(table.size(), 3);
+    assert!(close(table.get(1, 2, false), 0.679));
+    assert!(close(table.get(1, 2, true), 0.5122));
+    assert!(close(table.get(3, 1, true), 1.0 - 0.6914));
+    assert!(close(table.get(2, 3, true), 0.5961));
+    assert_eq!(table.get(0, 2, false), 1.0);
+    assert_eq!(table.get(2, 0, false), 0.0);
+    // Past the end of the table.
+    assert_eq!(table.get(9, 7, false), table.get(3, 3, false));
+
+    assert_eq!(table.to_string().parse(), Ok(table));
+}
+
+#[test]
+fn rejects_malformed_tables() {
+    let error = |text: &str| {
+        let e = text.parse::<MatchEquityTable>().unwrap_err();
+        (e.line, e.kind)
+    };
+
+    assert_eq!(
+        error(&TABLE.replace("59.61", "59,61")),
+        (5, MetErrorKind::Syntax("59,61".to_owned()))
+    );
+    assert_eq!(
+        error(&TABLE.replace("75.19", "175.19")),
+        (4, MetErrorKind::Range(175.19))
+    );
+    assert_eq!(
+        error(&TABLE.replace(" 69.14", "")),
+        (
+            8,
+            MetErrorKind::RowLength {
+                expected: 3,
+                found: 2
+            }
+        )
+    );
+    assert_eq!(error("pre-crawford\n50.00\n"), (2, MetErrorKind::Missing));
+}
+
+#[test]
+fn model_tables_are_consistent() {
+    let table = MatchEquityTable::default();
+    assert_eq!(table.size(), 25);
+
+    for a in 1..=25 {
+        assert!(close(table.get(a, a, false), 0.5));
+        for b in 1..=25 {
+            let sum = table.get(a, b, false) + table.get(b, a, false);
+            assert!(close(sum, 1.0));
+            if a < b {
+                assert!(table.get(a, b, false) > 0.5);
+                assert!(table.get(a, b, true) >= 0.5);
+            }
+        }
+    }
+}
+
+#[test]
+fn converts_game_chances_to_match_chances() {
+    let table: MatchEquityTable = TABLE.parse().unwrap();
+
+    // At 2-away 2-away, winning a gammon wins the match.
+    let two_away = state(3, [1, 1]);
+    let gammon = Probabilities {
+        win_gammon: 1.0,
+        ..Probabilities::single(1.0)
+    };
+    assert_eq!(table.mwc(&two_away, true, &gammon), 1.0);
+    assert!(close(
+        table.mwc(&two_away, true, &Probabilities::single(1.0)),
+        0.679
+    ));
+    assert!(close(table.score_mwc(&two_away, false), 0.5));
+
+    let equity = |p| table.equity(&two_away, true, &Probabilities::single(p));
+    assert!(close(equity(1.0), 1.0));
+    assert!(close(equity(0.0), -1.0));
+    assert!(close(equity(0.5), 0.0));
+    assert!(table.equity(&two_away, true, &gammon) > 1.0);
+
+    // Light is 3-away against Dark, 1-away, in the Crawford game.
+    let crawford = MatchState {
+        crawford: true,
+        ..state(3, [0, 2])
+    };
+    assert!(close(table.score_mwc(&crawford, true), 0.2481));
+    assert!(close(
+        table.mwc(&crawford, true, &Probabilities::single(1.0)),
+        1.0 - 0.5122
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Money play")]
+fn money_play_has_no_match_equity() {
+    MatchEquityTable::default().score_mwc(&state(0, [0, 0]), true);
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use std::collections::HashSet;
+
+use backgammon_sim::{
+    i8_based::notation::format_play, Backgammon, Evaluator, GameState,
+    MoveBuffer,
+};
+use common::sample_positions;
+
+fn replay(state: GameState, turn: bool, play: &[[u8; 2]]) -> GameState {
+    play.iter()
+        .fold(state, |s, &[from, die]| s.do_move(turn, from, die).unwrap())
+}
+
+#[test]
+fn play_iterator_yields_each_position_once_with_its_play() {
+    let mut moves = MoveBuffer::new();
+
+    let start = (GameState::new_with_default_setup(), true);
+    for (state, turn) in [start].into_iter().chain(sample_positions(11, 5)) {
+        for d1 in 1..=6 {
+            for d2 in d1..=6 {
+                moves.generate(turn, state, [d1, d2]);
+
+                let states: HashSet<_> = moves.state_iterator().collect();
+                let plays: Vec<_> = moves.play_iterator().collect();
+
+                assert_eq!(moves.state_iterator().count(), states.len());
+                assert_eq!(plays.len(), states.len());
+                for (play, new_state) in &plays {
+                    assert!(states.contains(new_state));
+                    assert_eq!(replay(state, turn, play), *new_state);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn both_sides_have_the_same_opening_plays() {
+    let state = GameState::new_with_default_setup();
+    let mut moves = MoveBuffer::new();
+
+    for d1 in 1..=6 {
+        for d2 in d1..=6 {
+            moves.generate(true, state, [d1, d2]);
+            let light = moves.state_iterator().count();
+            moves.generate(false, state, [d1, d2]);
+            let dark = moves.state_iterator().count();
+
+            assert_eq!(light, dark, "dice: {d1}-{d2}");
+        }
+    }
+}
+
+#[test]
+fn duplicates_removed_counts_transpositions() {
+    let state = GameState::new_with_default_setup();
+    let mut moves = MoveBuffer::new();
+
+    // 24/23 13/11 can also be played as 13/11 24/23.
+    moves.generate(true, state, [2, 1]);
+    assert!(moves.duplicates_removed() > 0);
+
+    // Doubles never generate reorderings in the first place.
+    moves.generate(true, state, [3, 3]);
+    assert_eq!(moves.duplicates_removed(), 0);
+}
+
+#[test]
+fn play_iterator_without_moves_yields_empty_play() {
+    // All of Light is on the bar against a closed board.
+    let mut tiles = [0; 24];
+    tiles[..6].copy_from_slice(&[-2; 6]);
+    tiles[23] = -3;
+    let state = Backgammon::from_parts(tiles, [15, 0], [0, 0]);
+
+    let mut moves = MoveBuffer::new();
+    moves.generate(true, state, [6, 1]);
+
+    let plays: Vec<_> = moves.play_iterator().collect();
+    assert_eq!(plays.len(), 1);
+    assert!(plays[0].0.is_empty());
+    assert_eq!(plays[0].1, state);
+}
+
+#[test]
+fn best_move_reports_the_play() {
+    let state = GameState::new_with_default_setup();
+
+    let mut ev = Evaluator::new();
+    let mut moves = MoveBuffer::new();
+
+    let (play, new_state, _) = ev
+        .get_best_move(state, true, [6, 5], 1, &mut moves)
+        .unwrap();
+
+    assert_eq!(replay(state, true, &play), new_state);
+    assert_eq!(format_play(state, true, &play), "24/13");
+}
@@ -0,0 +1,161 @@
+use backgammon_sim::{Backgammon, GameState, MoveError, SPECIAL_MOVE};
+
+#[test]
+fn rejects_illegal_space() {
+    let state = GameState::new_with_default_setup();
+
+    assert_eq!(
+        state.do_move(true, 100, 6),
+        Err(MoveError::IllegalSpace {
+            turn: true,
+            from: 100,
+            die: 6
+        })
+    );
+}
+
+#[test]
+fn rejects_occupied_target() {
+    let state = GameState::new_with_default_setup();
+
+    assert_eq!(
+        state.do_move(true, 0, 5),
+        Err(MoveError::TargetOccupied {
+            turn: true,
+            from: 0,
+            to: 5,
+            die: 5
+        })
+    );
+    assert_eq!(
+        state.do_move(false, 23, 5),
+        Err(MoveError::TargetOccupied {
+            turn: false,
+            from: 23,
+            to: 18,
+            die: 5
+        })
+    );
+}
+
+#[test]
+fn rejects_moving_from_empty_or_opposing_point() {
+    let state = GameState::new_with_default_setup();
+
+    assert!(matches!(
+        state.do_move(true, 1, 1),
+        Err(MoveError::NoMovablePieces { from: 1, .. })
+    ));
+    assert!(matches!(
+        state.do_move(true, 5, 1),
+        Err(MoveError::NoMovablePieces { from: 5, .. })
+    ));
+}
+
+#[test]
+fn rejects_entering_without_captured_pieces() {
+    let state = GameState::new_with_default_setup();
+
+    assert!(matches!(
+        state.do_move(false, SPECIAL_MOVE, 3),
+        Err(MoveError::NoCapturedPieces { turn: false, .. })
+    ));
+}
+
+#[test]
+fn rejects_moving_before_entering() {
+    let mut tiles = [0; 24];
+    tiles[0] = 1;
+    tiles[11] = 5;
+    tiles[23] = -2;
+    let state = GameState::from_parts(tiles, [1, 0], [8, 13]);
+
+    assert_eq!(
+        state.do_move(true, 11, 2),
+        Err(MoveError::PiecesCaptured {
+            turn: true,
+            from: 11,
+            die: 2
+        })
+    );
+    assert!(state.do_move(true, SPECIAL_MOVE, 2).is_ok());
+}
+
+#[test]
+fn rejects_bearing_off_early() -> Result<(), MoveError> {
+    let state = GameState::new_with_default_setup();
+
+    assert!(matches!(
+        state.do_move(true, 18, 6),
+        Err(MoveError::NotAllHome { .. })
+    ));
+
+    let state = state
+        .do_move(true, 0, 6)?
+        .do_move(true, 0, 6)?
+        .do_move(true, 6, 12)?
+        .do_move(true, 6, 12)?;
+    let state = (0..5).try_fold(state, |s, _| s.do_move(true, 11, 10))?;
+    let state = (0..3).try_fold(state, |s, _| s.do_move(true, 16, 3))?;
+    let state = state.do_move(true, 19, 5)?;
+
+    let err = state.do_move(true, 19, 6).unwrap_err();
+    assert_eq!(
+        err,
+        MoveError::FullMovesAvailable {
+            turn: true,
+            from: 19,
+            die: 6
+        }
+    );
+    assert_eq!((err.turn(), err.from(), err.die()), (true, 19, 6));
+
+    Ok(())
+}
+
+#[test]
+fn dark_bears_off_with_a_larger_die() -> Result<(), MoveError> {
+    let mut checkers = [0; 24];
+    checkers[23] = 15;
+    checkers[2] = -3;
+    checkers[0] = -2;
+    let state = GameState::from_parts(checkers, [0, 0], [0, 10]);
+
+    let state = state.do_move(false, 2, 6)?;
+    assert_eq!(state.checkers(2), -2);
+    assert_eq!(state.borne_off(), [0, 11]);
+    assert_eq!(state.pip_counts()[1], 8);
+
+    Ok(())
+}
+
+#[test]
+fn dark_uses_full_moves_before_bearing_off_with_a_larger_die() {
+    let mut checkers = [0; 24];
+    checkers[23] = 15;
+    checkers[4] = -1;
+    checkers[2] = -4;
+    let state = GameState::from_parts(checkers, [0, 0], [0, 10]);
+
+    assert_eq!(
+        state.do_move(false, 2, 6),
+        Err(MoveError::FullMovesAvailable {
+            turn: false,
+            from: 2,
+            die: 6
+        })
+    );
+}
+
+#[test]
+fn is_a_std_error() {
+    let state = GameState::new_with_default_setup();
+
+    let err: Box<dyn std::error::Error> =
+        state.do_move(true, 0, 5).unwrap_err().into();
+
+    assert_eq!(
+        err.to_string(),
+        "Target spot 5 occupied, Light cannot move 5 from 0"
+    );
+}
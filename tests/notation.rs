@@ -0,0 +1,197 @@
+mod common;
+
+use std::collections::HashSet;
+
+use backgammon_sim::{
+    i8_based::notation::{
+        format_play, parse_moves, parse_play, point_index, point_number,
+        NotatedMove, NotationError, BAR_POINT,
+    },
+    Backgammon, GameState, MoveError, Play, SPECIAL_MOVE,
+};
+use common::position;
+
+fn play_steps(state: GameState, turn: bool, steps: &[[u8; 2]]) -> GameState {
+    steps
+        .iter()
+        .fold(state, |s, &[from, die]| s.do_move(turn, from, die).unwrap())
+}
+
+#[test]
+fn point_numbers_are_from_the_movers_side() {
+    assert_eq!(point_number(true, 0), 24);
+    assert_eq!(point_number(true, 23), 1);
+    assert_eq!(point_number(false, 0), 1);
+    assert_eq!(point_number(false, 23), 24);
+    assert_eq!(point_number(false, SPECIAL_MOVE), BAR_POINT);
+
+    for turn in [true, false] {
+        for i in (0..24).chain([SPECIAL_MOVE]) {
+            assert_eq!(point_index(turn, point_number(turn, i)), Some(i));
+        }
+    }
+    assert_eq!(point_index(true, 0), None);
+}
+
+#[test]
+fn parses_move_syntax() {
+    let m = |from, to, hit| NotatedMove { from, to, hit };
+
+    assert_eq!(
+        parse_moves("24/18 13/11*").unwrap(),
+        [m(24, 18, false), m(13, 11, true)]
+    );
+    assert_eq!(
+        parse_moves("bar/22 6/off").unwrap(),
+        [m(25, 22, false), m(6, 0, false)]
+    );
+    assert_eq!(
+        parse_moves("13/7*/5 8/5(2)").unwrap(),
+        [
+            m(13, 7, true),
+            m(7, 5, false),
+            m(8, 5, false),
+            m(8, 5, false)
+        ]
+    );
+    assert_eq!(parse_moves("").unwrap(), []);
+
+    for bad in ["24", "18/24", "24/x", "8/5(0)", "8/5(2", "off/3", "3/bar"] {
+        assert_eq!(
+            parse_moves(bad),
+            Err(NotationError::Syntax(bad.to_owned())),
+            "{bad}"
+        );
+    }
+    assert_eq!(parse_moves("8/5(5)"), Err(NotationError::TooManyMoves));
+}
+
+#[test]
+fn opening_moves_for_both_players() {
+    let start = GameState::new_with_default_setup();
+
+    let light = play_steps(start, true, &[[16, 3], [18, 1]]);
+    assert_eq!(format_play(start, true, &[[16, 3], [18, 1]]), "8/5 6/5");
+    assert_eq!(parse_play(start, true, [3, 1], "8/5 6/5").unwrap().1, light);
+    assert_eq!(parse_play(start, true, [1, 3], "6/5 8/5").unwrap().1, light);
+
+    let dark = play_steps(start, false, &[[7, 3], [5, 1]]);
+    assert_eq!(format_play(start, false, &[[7, 3], [5, 1]]), "8/5 6/5");
+    assert_eq!(parse_play(start, false, [3, 1], "8/5 6/5").unwrap().1, dark);
+
+    let (play, state) = parse_play(start, true, [3, 2], "13/8").unwrap();
+    assert_eq!(play.len(), 2);
+    assert_eq!(state, play_steps(start, true, &[[11, 3], [14, 2]]));
+    assert_eq!(format_play(start, true, &play), "13/8");
+
+    let (play, _) = parse_play(start, true, [3, 3], "8/5(2) 6/3(2)").unwrap();
+    assert_eq!(format_play(start, true, &play), "8/5(2) 6/3(2)");
+}
+
+#[test]
+fn hits_bar_and_bear_off() {
+    // Light on roll with a checker on the bar and a Dark blot on its 11 point.
+    let state =
+        position(&[(18, 4), (11, 2), (13, -1), (6, -14)], [1, 0], [8, 0]);
+
+    let (play, new_state) =
+        parse_play(state, true, [3, 2], "bar/22 13/11*").unwrap();
+    assert_eq!(format_play(state, true, &play), "bar/22 13/11*");
+    assert_eq!(new_state.bar(), [0, 1]);
+
+    // Bearing off with a larger die once everything is home.
+    let state = position(&[(20, 2), (22, 3), (0, -5)], [0, 0], [10, 10]);
+    let (play, _) = parse_play(state, true, [6, 2], "4/off 2/off").unwrap();
+    assert_eq!(format_play(state, true, &play), "4/off 2/off");
+}
+
+#[test]
+fn rejects_unplayable_and_illegal_plays() {
+    let start = GameState::new_with_default_setup();
+
+    assert_eq!(
+        parse_play(start, true, [6, 5], "24/19 13/7"),
+        Err(NotationError::Unplayable(Some(MoveError::TargetOccupied {
+            turn: true,
+            from: 0,
+            to: 5,
+            die: 5
+        })))
+    );
+    // The message numbers points as the play did.
+    assert_eq!(
+        parse_play(start, false, [6, 5], "24/19 13/7")
+            .unwrap_err()
+            .to_string(),
+        "Unplayable: Target spot 19 occupied, Dark cannot move 5 from 24"
+    );
+    assert_eq!(
+        parse_play(start, true, [6, 5], "13/10"),
+        Err(NotationError::Unplayable(None))
+    );
+    assert_eq!(
+        parse_play(start, true, [6, 5], "24/18"),
+        Err(NotationError::Illegal)
+    );
+}
+
+// Every distinct play of every roll from the start formats to text that
+// parses back to the same position.
+#[test]
+fn formatting_round_trips() {
+    fn plays(
+        state: GameState,
+        turn: bool,
+        dice: &[u8],
+        play: &mut Play,
+        seen: &mut HashSet<(GameState, Vec<u8>)>,
+        out: &mut Vec<(Play, GameState)>,
+    ) {
+        if !seen.insert((state, dice.to_vec())) {
+            return;
+        }
+
+        let mut moved = false;
+        for (i, &die) in dice.iter().enumerate() {
+            for [from, _] in state.legal_moves(turn, die) {
+                moved = true;
+                let mut rest = dice.to_vec();
+                rest.remove(i);
+                play.push([from, die]);
+                let new_state = state.do_move(turn, from, die).unwrap();
+                plays(new_state, turn, &rest, play, seen, out);
+                play.pop();
+            }
+        }
+        if !moved {
+            out.push((play.clone(), state));
+        }
+    }
+
+    let start = GameState::new_with_default_setup();
+    for turn in [true, false] {
+        for d1 in 1..=6 {
+            for d2 in d1..=6 {
+                let dice = if d1 == d2 { vec![d1; 4] } else { vec![d1, d2] };
+                let mut all = Vec::new();
+                plays(
+                    start,
+                    turn,
+                    &dice,
+                    &mut Play::new(),
+                    &mut HashSet::new(),
+                    &mut all,
+                );
+
+                let legal = start.legal_plays(turn, [d1, d2]);
+                for (play, state) in
+                    all.into_iter().filter(|(_, s)| legal.contains(s))
+                {
+                    let text = format_play(start, turn, &play);
+                    let parsed = parse_play(start, turn, [d1, d2], &text);
+                    assert_eq!(parsed.map(|(_, s)| s), Ok(state), "{text}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use backgammon_sim::{
+    differential::random_position, i8_based::PositionIdError, rng::Rng,
+    Backgammon, GameState,
+};
+
+#[test]
+fn encodes_the_starting_position() {
+    let state = GameState::new_with_default_setup();
+
+    assert_eq!(state.to_position_id(true), "4HPwATDgc/ABMA");
+    assert_eq!(state.to_position_id(false), "4HPwATDgc/ABMA");
+    assert_eq!(
+        GameState::from_position_id("4HPwATDgc/ABMA", true),
+        Ok(state)
+    );
+}
+
+#[test]
+fn encodes_an_opening_move() -> Result<(), Box<dyn std::error::Error>> {
+    // Light plays 3-1 as 8/5 6/5, leaving Dark on roll.
+    let state = GameState::new_with_default_setup()
+        .do_move(true, 16, 3)?
+        .do_move(true, 18, 1)?;
+
+    assert_eq!(state.to_position_id(false), "sGfwATDgc/ABMA");
+    assert_eq!(GameState::from_position_id("sGfwATDgc/ABMA", false)?, state);
+
+    Ok(())
+}
+
+#[test]
+fn random_positions_round_trip() {
+    let mut rng = Rng::new(13);
+
+    for _ in 0..200 {
+        let state = random_position(&mut rng);
+
+        for turn in [true, false] {
+            let id = state.to_position_id(turn);
+            assert_eq!(id.len(), 14);
+            assert_eq!(GameState::from_position_id(&id, turn), Ok(state));
+        }
+    }
+}
+
+#[test]
+fn keeps_checkers_on_the_bar() {
+    let mut tiles = [0; 24];
+    tiles[18] = 13;
+    tiles[2] = -14;
+    let state = GameState::from_parts(tiles, [2, 1], [0, 0]);
+
+    let id = state.to_position_id(true);
+    let decoded = GameState::from_position_id(&id, true).unwrap();
+
+    assert_eq!(decoded.bar(), [2, 1]);
+    assert_eq!(decoded, state);
+}
+
+#[test]
+fn rejects_malformed_ids() {
+    assert_eq!(
+        GameState::from_position_id("4HPwATDgc/AB", true),
+        Err(PositionIdError::Length(12))
+    );
+    assert_eq!(
+        GameState::from_position_id("4HPwATDgc/AB-A", true),
+        Err(PositionIdError::Character('-'))
+    );
+    assert_eq!(
+        GameState::from_position_id("//////////////", true),
+        Err(PositionIdError::TooManyCheckers { player: false })
+    );
+}
@@ -0,0 +1,58 @@
+use backgammon_sim::{
+    cube::Chances, probabilities::Probabilities, Backgammon, Evaluator,
+    GameState,
+};
+
+const PROBS: Probabilities = Probabilities {
+    win: 0.6,
+    win_gammon: 0.2,
+    win_backgammon: 0.05,
+    lose_gammon: 0.1,
+    lose_backgammon: 0.01,
+};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn equity_counts_gammons_and_backgammons() {
+    assert!(close(PROBS.cubeless_equity(), 0.2 + 0.25 - 0.11));
+    assert!(close(PROBS.flip().cubeless_equity(), -0.34));
+    assert_eq!(PROBS.flip().flip(), PROBS);
+
+    let total: f64 = PROBS.outcomes().iter().map(|&(p, _)| p).sum();
+    assert!(close(total, 1.0));
+
+    let chances = Chances::from(&PROBS);
+    assert!(close(chances.win_value, 0.85 / 0.6));
+    assert!(close(chances.loss_value, 0.51 / 0.4));
+    assert!(close(chances.cubeless_equity(), PROBS.cubeless_equity()));
+}
+
+#[test]
+fn races_have_gammon_chances() {
+    let mut ev = Evaluator::new();
+
+    // Dark has passed Light's checkers but is still far from home.
+    let mut checkers = [0; 24];
+    checkers[19] = 5;
+    checkers[21] = 5;
+    checkers[23] = 5;
+    checkers[14] = -8;
+    checkers[16] = -7;
+    let race = GameState::from_parts(checkers, [0, 0], [0, 0]);
+
+    let eval = ev.get_brute_force_eval(race, true, 0);
+    assert!(eval.win > 0.99);
+    assert!(eval.win_gammon > 0.5);
+    assert_eq!(eval.win_backgammon, 0.0);
+    assert!(eval.lose_gammon < 1e-6);
+    assert!(ev.get_brute_force_eval(race, false, 0).win < 0.01);
+
+    // With contact, the pip count gives no gammons.
+    let start = GameState::new_with_default_setup();
+    let eval = ev.get_brute_force_eval(start, true, 1);
+    assert!(eval.win > 0.5);
+    assert_eq!(eval.win_gammon + eval.lose_gammon, 0.0);
+}
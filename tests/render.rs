@@ -0,0 +1,107 @@
+use std::io::Cursor;
+
+use backgammon_sim::{
+    enum_based,
+    game::{HumanPlayer, Player},
+    render::{Renderer, Theme},
+    Backgammon, GameState, MoveBuffer,
+};
+
+const START_ASCII: &str =
+    " +13-14-15-16-17-18------19-20-21-22-23-24-+     O: Dark
+ | X           O    |   | O              X |     pips 167, bar 0, off 0
+ | X           O    |   | O              X |
+ | X           O    |   | O                |
+ | X                |   | O                |
+ | X                |   | O                |
+ |                  |BAR|                  |
+ | O                |   | X                |
+ | O                |   | X                |
+ | O           X    |   | X                |
+ | O           X    |   | X              O |
+ | O           X    |   | X              O |     pips 167, bar 0, off 0
+ +12-11-10--9--8--7-------6--5--4--3--2--1-+     X: Light";
+
+#[test]
+fn display_uses_the_default_renderer() {
+    let state = GameState::new_with_default_setup();
+    let enum_state = enum_based::GameState::new_with_default_setup();
+
+    assert_eq!(state.to_string(), Renderer::default().render(&state));
+    assert_eq!(enum_state.to_string(), state.to_string());
+    assert!(state.to_string().contains("\x1b["));
+}
+
+#[test]
+fn draws_ascii_boards() {
+    let state = GameState::new_with_default_setup();
+
+    assert_eq!(
+        Renderer::new(Theme::Ascii, true).render(&state),
+        START_ASCII
+    );
+
+    // Swapping sides gives the same picture for the symmetric start.
+    let dark = Renderer::new(Theme::Ascii, false).render(&state);
+    assert_eq!(
+        dark,
+        START_ASCII
+            .replace("Dark", "?")
+            .replace("Light", "Dark")
+            .replace('?', "Light")
+    );
+}
+
+#[test]
+fn numbers_points_from_the_perspective() {
+    // Light has moved a back checker from its 24 point to its 18 point.
+    let state = GameState::new_with_default_setup()
+        .do_move(true, 0, 6)
+        .unwrap();
+
+    let light = Renderer::new(Theme::Unicode, true).render(&state);
+    assert!(light.contains("24║○--------------"));
+    assert!(light.contains("18║○--------------"));
+    assert!(!light.contains('\x1b'));
+
+    let dark = Renderer::new(Theme::Unicode, false).render(&state);
+    assert!(dark.contains("24║●●-------------"));
+    assert!(dark.contains("--------------○║7"));
+    assert!(dark.contains("║HOME LIGHT       ║        HOME DARK║"));
+}
+
+#[test]
+fn counts_tall_stacks() {
+    let mut checkers = [0; 24];
+    checkers[23] = 15;
+    checkers[0] = -15;
+    let state = GameState::from_parts(checkers, [0, 0], [0, 0]);
+
+    let board = Renderer::new(Theme::Ascii, true).render(&state);
+    let lines: Vec<_> = board.lines().collect();
+    assert_eq!(lines[5], " |                  |   |               15 |");
+    assert_eq!(lines[7], " |                  |   |               15 |");
+}
+
+#[test]
+fn human_player_shows_the_theme() {
+    let state = GameState::new_with_default_setup();
+    let mut moves = MoveBuffer::new();
+    moves.generate(false, state, [3, 1]);
+
+    let mut output = Vec::new();
+    HumanPlayer::new(Cursor::new("0\n"), &mut output)
+        .with_theme(Theme::Ascii)
+        .choose_move(&moves);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.is_ascii());
+    assert!(output.contains("X: Dark"));
+}
+
+#[test]
+fn parses_theme_names() {
+    assert_eq!("ASCII".parse(), Ok(Theme::Ascii));
+    assert_eq!("unicode".parse(), Ok(Theme::Unicode));
+    assert!("html".parse::<Theme>().is_err());
+}
@@ -0,0 +1,52 @@
+use std::io::Cursor;
+
+use backgammon_sim::{dice::ScriptedDice, game::Repl, render::Theme};
+
+fn run(input: &str, rolls: &[[u8; 2]]) -> String {
+    let mut output = Vec::new();
+    let record = Repl::new(Cursor::new(input), &mut output)
+        .with_theme(Theme::Ascii)
+        .with_depth(1)
+        .play(&mut ScriptedDice::new(rolls.iter().copied()))
+        .unwrap();
+    assert_eq!(record, None);
+
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn rejects_illegal_plays_with_the_reason() {
+    let output = run("13/12 8/5\n8/5 6/5\nquit\n", &[[3, 1], [6, 5], [2, 2]]);
+
+    assert!(output.contains("Light rolls 3, Dark rolls 1: Light starts"));
+    assert!(output.contains(
+        "Light to play 3-1: Unplayable: Target spot 12 occupied, Light \
+         cannot move 1 from 13"
+    ));
+    assert!(output.contains("Dark rolls 6-5 and plays 24/13"));
+    assert!(output.contains("Light to play 2-2: "));
+}
+
+#[test]
+fn undo_takes_back_the_last_play() {
+    let output = run(
+        "undo\n8/5 6/5\nundo\n24/21 24/23\nquit\n",
+        &[[3, 1], [6, 5], [4, 4], [2, 1], [5, 2]],
+    );
+
+    assert!(output.contains("Light to play 3-1: Nothing to undo"));
+    assert_eq!(output.matches("Light to play 3-1").count(), 3);
+    assert!(output.contains("Dark rolls 6-5"));
+    assert!(output.contains("Light to play 4-4: "));
+    assert!(output.contains("Dark rolls 2-1"));
+    assert!(output.contains("Light to play 5-2: "));
+}
+
+#[test]
+fn hints_list_the_best_plays() {
+    let output = run("hint\n", &[[3, 1]]);
+
+    assert!(output.contains("  1. "));
+    assert!(output.contains("  3. "));
+    assert!(!output.contains("  4. "));
+}
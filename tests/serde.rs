@@ -0,0 +1,73 @@
+#![cfg(feature = "serde")]
+
+use backgammon_sim::{
+    dice::SeededDice,
+    game::{play_game, Action, GameRecord, PipCountPlayer, RandomPlayer},
+    match_state::MatchState,
+    rng::Rng,
+    GameState,
+};
+use serde_json::json;
+
+#[test]
+fn positions_follow_the_schema() {
+    let state = GameState::new_with_default_setup();
+    let value = serde_json::to_value(state).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "checkers": [2, 0, 0, 0, 0, -5, 0, -3, 0, 0, 0, 5,
+                         -5, 0, 0, 0, 3, 0, 5, 0, 0, 0, 0, -2],
+            "bar": [0, 0],
+            "borne_off": [0, 0],
+        })
+    );
+    assert_eq!(serde_json::from_value::<GameState>(value).unwrap(), state);
+}
+
+#[test]
+fn rejects_positions_with_missing_checkers() {
+    let value = json!({
+        "checkers": [2, 0, 0, 0, 0, -5, 0, -3, 0, 0, 0, 5,
+                     -5, 0, 0, 0, 3, 0, 5, 0, 0, 0, 0, -2],
+        "bar": [0, 0],
+        "borne_off": [1, 0],
+    });
+
+    let e = serde_json::from_value::<GameState>(value).unwrap_err();
+    assert!(e.to_string().contains("Light"), "{e}");
+}
+
+#[test]
+fn game_records_round_trip() {
+    let mut light = RandomPlayer::new(Rng::new(4));
+    let mut dark = PipCountPlayer;
+    let record = play_game([&mut light, &mut dark], &mut SeededDice::new(4));
+
+    let text = serde_json::to_string(&record).unwrap();
+    assert_eq!(serde_json::from_str::<GameRecord>(&text).unwrap(), record);
+
+    let value = serde_json::to_value(&record).unwrap();
+    let first = &record.turns[0];
+    assert_eq!(value["turns"][0]["turn"], json!(first.turn));
+    assert_eq!(value["turns"][0]["dice"], json!(first.dice));
+    assert!(value["result"]["kind"].is_string());
+}
+
+#[test]
+fn actions_and_match_states_round_trip() {
+    let action = Action::Double {
+        player: true,
+        value: 2,
+    };
+    let value = serde_json::to_value(&action).unwrap();
+    assert_eq!(value, json!({"double": {"player": true, "value": 2}}));
+    assert_eq!(serde_json::from_value::<Action>(value).unwrap(), action);
+
+    let state: MatchState = "4HPwATDgc/ABMA:QYkqASAAIAAA".parse().unwrap();
+    let value = serde_json::to_value(state).unwrap();
+    assert_eq!(value["phase"], json!("playing"));
+    assert_eq!(value["dice"], json!([5, 2]));
+    assert_eq!(serde_json::from_value::<MatchState>(value).unwrap(), state);
+}
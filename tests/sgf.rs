@@ -0,0 +1,149 @@
+use backgammon_sim::{
+    dice::SeededDice,
+    game::{
+        play_game,
+        sgf::{SgfErrorKind, SgfFile, SgfGame},
+        Action, PipCountPlayer, RandomPlayer,
+    },
+    rng::Rng,
+    Backgammon, GameState,
+};
+
+const OPENING: &str = "\
+(;FF[4]GM[6]MI[length:3][game:0][ws:0][bs:0]PW[Alice]PB[Bob]RE[W+1]
+;W[31qtst]C[The best opening roll]
+;B[52mkmh]
+;W[double];B[drop])
+";
+
+fn error(text: &str) -> (usize, SgfErrorKind) {
+    let e = text.parse::<SgfFile>().unwrap_err();
+    (e.node, e.kind)
+}
+
+#[test]
+fn reads_moves_cube_actions_and_comments() {
+    let file: SgfFile = OPENING.parse().unwrap();
+    let game = &file.games[0];
+
+    assert_eq!(game.names, ["Alice", "Bob"]);
+    assert_eq!(game.match_length, 3);
+    assert_eq!(game.winner, Some((true, 1)));
+    assert_eq!(game.start, GameState::new_with_default_setup());
+    assert_eq!(game.moves.len(), 4);
+    assert_eq!(
+        game.moves[0].comment.as_deref(),
+        Some("The best opening roll")
+    );
+    assert_eq!(
+        game.moves[2].action,
+        Action::Double {
+            player: true,
+            value: 2
+        }
+    );
+
+    // 8/5 6/5 for Light and 13/11 13/8 for Dark.
+    let state = game.final_state();
+    assert_eq!(state.checkers(19), 2);
+    assert_eq!(state.checkers(16), 2);
+    assert_eq!(state.checkers(12), -3);
+    assert_eq!(state.checkers(10), -1);
+    assert_eq!(state.checkers(7), -4);
+
+    assert_eq!(file.to_string().parse(), Ok(file));
+}
+
+#[test]
+fn saves_comments_on_self_play() {
+    let mut dice = SeededDice::new(11);
+    let mut light = RandomPlayer::new(Rng::new(12));
+    let mut dark = PipCountPlayer;
+
+    let records: Vec<_> = (0..2)
+        .map(|_| play_game([&mut light, &mut dark], &mut dice))
+        .collect();
+
+    let mut file = SgfFile {
+        games: records
+            .iter()
+            .map(|record| SgfGame::from_record(["Random", "Pip"], record))
+            .collect(),
+    };
+    for (i, m) in file.games[0].moves.iter_mut().enumerate() {
+        m.comment = Some(format!("Move {i} [eq: 0.5] \\ end"));
+    }
+
+    let parsed: SgfFile = file.to_string().parse().unwrap();
+    assert_eq!(parsed, file);
+    assert_eq!(parsed.games[1].final_state(), records[1].final_state());
+}
+
+#[test]
+fn sets_up_positions() {
+    let text = "(;GM[6]AE[a:y]AW[x][x][w]AB[a][b]RE[B+1]\
+                ;W[21xzwz];B[11baazaz])";
+    let file: SgfFile = text.parse().unwrap();
+    let game = &file.games[0];
+
+    assert_eq!(game.start.borne_off(), [12, 13]);
+    assert_eq!(game.start.checkers(23), 2);
+    assert_eq!(game.final_state().borne_off(), [14, 15]);
+
+    assert_eq!(file.to_string().parse(), Ok(file));
+}
+
+#[test]
+fn follows_the_main_line() {
+    let file: SgfFile = "(;GM[6](;W[31qtst])(;W[31qtsu]))".parse().unwrap();
+    assert_eq!(file.games[0].moves.len(), 1);
+}
+
+#[test]
+fn rejects_invalid_games() {
+    assert_eq!(error("(;GM[1])"), (0, SgfErrorKind::NotBackgammon));
+    assert_eq!(error("(;GM[6];W[31"), (1, SgfErrorKind::Syntax(12)));
+    assert_eq!(error("(;GM[6];W[31qtsu])"), (1, SgfErrorKind::Illegal));
+    assert_eq!(
+        error("(;GM[6];W[31qtst];W[42qusu])"),
+        (2, SgfErrorKind::OutOfTurn)
+    );
+    assert_eq!(error("(;GM[6];B[take])"), (1, SgfErrorKind::Cube));
+    assert_eq!(
+        error("(;GM[6]RE[W+1];W[31qtst];B[double];W[drop])"),
+        (0, SgfErrorKind::Winner)
+    );
+    assert_eq!(error("(;GM[6];W[31qtst];AB[a])"), (2, SgfErrorKind::Setup));
+}
+
+#[test]
+fn rejects_doubles_out_of_turn() {
+    // Before the opening roll.
+    assert_eq!(error("(;GM[6];W[double])"), (1, SgfErrorKind::OutOfTurn));
+    // Light has just rolled, so Dark is on roll.
+    assert_eq!(
+        error("(;GM[6];W[31qtst];W[double])"),
+        (2, SgfErrorKind::OutOfTurn)
+    );
+}
+
+#[test]
+fn rejects_doubles_in_the_crawford_game() {
+    let crawford = "(;GM[6]MI[length:3][ws:2][bs:0]RU[Crawford:CrawfordGame]\
+                    ;W[31qtst];B[double])";
+    assert_eq!(error(crawford), (2, SgfErrorKind::Cube));
+
+    let file: SgfFile =
+        "(;GM[6]MI[length:3][ws:2][bs:0]RU[Crawford:CrawfordGame];W[31qtst])"
+            .parse()
+            .unwrap();
+    assert!(file.games[0].crawford);
+    assert_eq!(file.to_string().parse(), Ok(file));
+
+    // Either player may double after the Crawford game.
+    let file: SgfFile =
+        "(;GM[6]MI[length:3][ws:2][bs:1]RU[Crawford];W[31qtst];B[double])"
+            .parse()
+            .unwrap();
+    assert!(!file.games[0].crawford);
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use backgammon_sim::{
+    i8_based::{
+        notation::format_play, Heuristic, NetErrorKind, NeuralNet, PipCount,
+        RaceFormula, StaticEval,
+    },
+    probabilities::Probabilities,
+    rng::Rng,
+    Backgammon, Evaluator, GameState, MoveBuffer,
+};
+use common::sample_positions;
+
+fn is_consistent(p: &Probabilities) -> bool {
+    let ordered = |chances: &[f64]| chances.windows(2).all(|w| w[0] >= w[1]);
+
+    ordered(&[1.0, p.win, p.win_gammon, p.win_backgammon, 0.0])
+        && ordered(&[1.0, 1.0 - p.win, p.lose_gammon, p.lose_backgammon, 0.0])
+}
+
+#[test]
+fn evaluators_give_consistent_chances() {
+    let net = NeuralNet::new(8, &mut Rng::new(1));
+    let evals: [&dyn StaticEval; 4] =
+        [&PipCount, &RaceFormula, &Heuristic::default(), &net];
+
+    let unfinished = sample_positions(21, 1)
+        .into_iter()
+        .filter(|(state, _)| state.result().is_none());
+    for (state, turn) in unfinished {
+        for eval in evals {
+            let p = eval.eval(&state, turn);
+            assert!(is_consistent(&p), "{p:?} for {state:?}");
+        }
+    }
+}
+
+#[test]
+fn race_formula_counts_wastage() {
+    // Both sides have 60 pips, but Light has stacked the low points.
+    let mut checkers = [0; 24];
+    checkers[23] = 6;
+    checkers[22] = 3;
+    checkers[21] = 2;
+    checkers[14] = 2;
+    checkers[13] = 2;
+    checkers[0] = -2;
+    checkers[1] = -2;
+    checkers[2] = -1;
+    checkers[3] = -3;
+    checkers[4] = -3;
+    checkers[5] = -4;
+    let race = GameState::from_parts(checkers, [0, 0], [0, 0]);
+    assert_eq!(race.pip_counts(), [60, 60]);
+
+    assert!(RaceFormula.eval(&race, true).win < PipCount.eval(&race, true).win);
+    assert!(
+        RaceFormula.eval(&race, false).win > PipCount.eval(&race, false).win
+    );
+
+    // Positions with contact are plain pip counts.
+    let start = GameState::new_with_default_setup();
+    assert_eq!(RaceFormula.eval(&start, true), PipCount.eval(&start, true));
+}
+
+#[test]
+fn heuristic_makes_points() {
+    let state = GameState::new_with_default_setup();
+    let mut evaluator = Evaluator::with_eval(Heuristic::default());
+
+    let (play, _, _) = evaluator
+        .get_best_move(state, true, [3, 1], 1, &mut MoveBuffer::new())
+        .unwrap();
+    assert_eq!(format_play(state, true, &play), "8/5 6/5");
+}
+
+#[test]
+fn neural_nets_learn_and_are_saved_as_text() {
+    let mut net = NeuralNet::new(8, &mut Rng::new(2));
+    let state = GameState::new_with_default_setup();
+
+    let before = net.eval(&state, true);
+    for _ in 0..20 {
+        net.train(&state, true, &Probabilities::single(1.0), 0.5);
+    }
+    assert!(net.eval(&state, true).win > before.win);
+
+    let text = net.to_string();
+    assert_eq!(text.parse(), Ok(net));
+
+    let error = |text: &str| {
+        let e = text.parse::<NeuralNet>().unwrap_err();
+        (e.line, e.kind)
+    };
+    let lines: Vec<_> = text.lines().collect();
+    // The first missing row is line 6.
+    assert_eq!(error(&lines[..5].join("\n")), (6, NetErrorKind::Missing));
+    assert_eq!(error(""), (1, NetErrorKind::Missing));
+    assert_eq!(
+        error(&text.replacen('\n', "\nx ", 1)),
+        (2, NetErrorKind::Syntax("x".to_owned()))
+    );
+    assert_eq!(
+        error("neural-net 10 8\n"),
+        (1, NetErrorKind::Syntax("neural-net 10 8".to_owned()))
+    );
+}
@@ -0,0 +1,59 @@
+use backgammon_sim::{
+    match_state::MatchState, render::SvgRenderer, Backgammon, GameState,
+};
+
+fn count(svg: &str, pattern: &str) -> usize {
+    svg.matches(pattern).count()
+}
+
+#[test]
+fn draws_every_checker_and_point() {
+    let svg =
+        SvgRenderer::new(true).render(&GameState::new_with_default_setup());
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(count(&svg, "<polygon"), 24);
+    assert_eq!(count(&svg, r#"r="17""#), 30);
+    assert!(svg.contains("Light: 167 pips, 0 off"));
+    // No dice or cube.
+    assert_eq!(count(&svg, r#"r="3""#), 0);
+    assert!(!svg.contains(">64<"));
+    // No stack is tall enough to need a count.
+    assert_eq!(count(&svg, r#"font-weight="bold" fill="#), 0);
+}
+
+#[test]
+fn shows_stack_sizes_bar_and_tray() {
+    let mut checkers = [0; 24];
+    checkers[23] = 9;
+    checkers[5] = -12;
+    let state = GameState::from_parts(checkers, [2, 1], [4, 2]);
+
+    let svg = SvgRenderer::new(false).render(&state);
+
+    // Five checkers per point and three on the bar.
+    assert_eq!(count(&svg, r#"r="17""#), 5 + 5 + 2 + 1);
+    // The counts are drawn on the top checker in the other player's colour,
+    // unlike the point labels.
+    assert_eq!(count(&svg, r#"font-weight="bold" fill="#), 2);
+    assert!(svg.contains(r##"font-weight="bold" fill="#2b2b2b">9</text>"##));
+    assert!(svg.contains(r##"font-weight="bold" fill="#f5ecd7">12</text>"##));
+    assert_eq!(count(&svg, r#"height="10""#), 6);
+    assert!(svg.contains("Dark: "));
+}
+
+#[test]
+fn draws_dice_and_cube_of_a_match() {
+    let state: MatchState = "4HPwATDgc/ABMA:QYkqASAAIAAA".parse().unwrap();
+    let svg = SvgRenderer::for_match(&state).render(&state.position);
+
+    // A five and a two.
+    assert_eq!(count(&svg, r#"width="32" height="32""#), 2);
+    assert_eq!(count(&svg, r#"r="3""#), 7);
+    assert!(svg.contains("font-weight=\"bold\">2</text>"));
+
+    let centred = MatchState::new(state.position, true, 0);
+    let svg = SvgRenderer::for_match(&centred).render(&centred.position);
+    assert!(svg.contains(">64</text>"));
+}